@@ -0,0 +1,67 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/*
+ Generates a test for every fixture in input/test, i.e. every `dayN.in`
+ and every `dayN-<name>.in`. The tests themselves only point
+ `utils::fixture::check` at the file, so the expected answers are read
+ from its `// expect` headers when the test runs.
+*/
+fn main() {
+    println!("cargo:rerun-if-changed=input/test");
+
+    let mut fixtures: Vec<(u8, Option<String>)> = fs::read_dir("input/test")
+        .expect("Cannot read input/test")
+        .filter_map(|entry| {
+            let file = entry.ok()?.file_name().into_string().ok()?;
+            fixture_of(&file)
+        })
+        .collect();
+    fixtures.sort();
+
+    let mut tests = String::new();
+    for (day, name) in fixtures {
+        let (test, file) = match name {
+            Some(name) => (
+                format!("day{}_{}", day, identifier(&name)),
+                format!("day{}-{}.in", day, name),
+            ),
+            None => (format!("day{}", day), format!("day{}.in", day)),
+        };
+
+        tests.push_str(&format!(
+            "#[test]\n\
+             fn {}() {{\n    \
+                 crate::utils::fixture::check({}, concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/input/test/{}\"));\n\
+             }}\n\n",
+            test, day, file
+        ));
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("fixtures.rs");
+    fs::write(out, tests).expect("Cannot write the fixture tests");
+}
+
+// `day12.in` is day 12's default fixture, `day12-small.in` a named one
+fn fixture_of(file: &str) -> Option<(u8, Option<String>)> {
+    let stem = file.strip_prefix("day")?.strip_suffix(".in")?;
+    let (day, name) = match stem.split_once('-') {
+        Some((day, name)) => (day, Some(name.to_string())),
+        None => (stem, None),
+    };
+
+    Some((day.parse().ok()?, name))
+}
+
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,161 @@
+use crate::error::{Error, Result};
+use crate::runner::{format_duration, guarded};
+use crate::solution::Puzzle;
+use crate::utils::input::Source;
+use crate::utils::params::Params;
+use crate::utils::table::{self, Align};
+use std::time::Duration;
+
+// Summary of a set of timing samples
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+
+        let len = samples.len();
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+
+        Stats {
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / len as u32,
+        }
+    }
+}
+
+pub struct Report {
+    pub day: u8,
+    pub name: &'static str,
+    pub iterations: usize,
+    pub read: Stats,
+    pub parse: Stats,
+    // None for parts that aren't solved yet
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+// Times every phase of a day `iterations` times. Reading and parsing happen
+// once per part, so they end up with twice as many samples as the parts.
+// Parts that aren't solved yet are left out altogether.
+pub fn bench(
+    puzzle: &dyn Puzzle,
+    source: &Source,
+    params: &Params,
+    iterations: usize,
+) -> Result<Report> {
+    let mut read = Vec::new();
+    let mut parse = Vec::new();
+    let mut part1 = Vec::new();
+    let mut part2 = Vec::new();
+
+    for _ in 0..iterations {
+        for (extra, samples) in [(false, &mut part1), (true, &mut part2)] {
+            let (_, timings) = match puzzle.run_timed(extra, source, params) {
+                Err(Error::Unsolved(_)) => continue,
+                timed => timed?,
+            };
+            read.push(timings.read);
+            parse.push(timings.parse);
+            samples.push(timings.solve);
+        }
+    }
+
+    Ok(Report {
+        day: puzzle.day(),
+        name: puzzle.name(),
+        iterations,
+        read: Stats::from_samples(read),
+        parse: Stats::from_samples(parse),
+        part1: Some(part1)
+            .filter(|s| !s.is_empty())
+            .map(Stats::from_samples),
+        part2: Some(part2)
+            .filter(|s| !s.is_empty())
+            .map(Stats::from_samples),
+    })
+}
+
+// Benchmarks every given puzzle and prints a report per day. Days that
+// fail (or panic) are reported and skipped.
+pub fn run(puzzles: &[&dyn Puzzle], source: &Source, params: &Params, iterations: usize) {
+    for puzzle in puzzles {
+        match guarded(|| bench(*puzzle, source, params, iterations)) {
+            Ok(Ok(report)) => print_report(&report),
+            Ok(Err(e)) => println!("Day {} ({}): {}\n", puzzle.day(), puzzle.name(), e),
+            Err(msg) => println!(
+                "Day {} ({}): panicked: {}\n",
+                puzzle.day(),
+                puzzle.name(),
+                msg
+            ),
+        }
+    }
+}
+
+fn print_report(report: &Report) {
+    println!(
+        "Day {} ({}), {} iterations",
+        report.day, report.name, report.iterations
+    );
+
+    let phases = [
+        ("read", Some(&report.read)),
+        ("parse", Some(&report.parse)),
+        ("part 1", report.part1.as_ref()),
+        ("part 2", report.part2.as_ref()),
+    ];
+    let rows: Vec<Vec<String>> = phases
+        .iter()
+        .map(|(phase, stats)| match stats {
+            Some(stats) => vec![
+                phase.to_string(),
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.mean),
+            ],
+            // Not solved yet
+            None => vec![
+                phase.to_string(),
+                "-".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+            ],
+        })
+        .collect();
+
+    table::print(
+        &[
+            ("Phase", Align::Left),
+            ("min", Align::Right),
+            ("median", Align::Right),
+            ("mean", Align::Right),
+        ],
+        &rows,
+    );
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis).to_vec();
+        let stats = Stats::from_samples(samples);
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+    }
+}
@@ -1,63 +1,85 @@
-use crate::utils::input;
+use crate::error::Result;
+use crate::solution::Solution;
+use crate::utils::input::{parse_number, Input, Stream};
 
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = input::read_file("1", test);
-    let input: Vec<i32> = input::convert_to_ints(&lines);
+pub struct Day1;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(input),
-            true => p2::run(input),
-        }
-    )
+impl Solution for Day1 {
+    type Input = Depths;
+
+    const DAY: u8 = 1;
+    const NAME: &'static str = "Sonar Sweep";
+    const STREAMS: bool = true;
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        Ok(Depths(input.stream()))
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input)?.to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input)?.to_string())
+    }
+}
+
+// The depth readings, parsed one at a time as they are read
+pub struct Depths(Stream);
+
+impl Iterator for Depths {
+    type Item = Result<i32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|line| {
+            let (n, line) = line?;
+            parse_number(&line).map_err(|e| e.at_line(n))
+        })
+    }
 }
 
 mod p1 {
+    use crate::error::Result;
+
     // Count increases in values
-    pub fn run(input: Vec<i32>) -> u32 {
+    pub fn run(depths: impl Iterator<Item = Result<i32>>) -> Result<u32> {
         let mut cnt: u32 = 0;
+        let mut previous: Option<i32> = None;
 
-        for i in 1..input.len() {
-            if input[i] > input[i - 1] {
+        for depth in depths {
+            let depth = depth?;
+            if previous.is_some_and(|previous| depth > previous) {
                 cnt += 1;
             }
+            previous = Some(depth);
         }
 
-        cnt
+        Ok(cnt)
     }
 }
 
 mod p2 {
+    use crate::error::Result;
+    use std::collections::VecDeque;
+
     // Count increases in values in windows of 3
-    pub fn run(input: Vec<i32>) -> u32 {
-        let mut current_window: i32 = input[0..=2].iter().sum();
+    pub fn run(depths: impl Iterator<Item = Result<i32>>) -> Result<u32> {
+        let mut window: VecDeque<i32> = VecDeque::with_capacity(4);
         let mut cnt: u32 = 0;
 
-        for i in 3..input.len() {
-            let previous_window = current_window;
-            current_window = current_window + input[i] - input[i - 3];
+        for depth in depths {
+            window.push_back(depth?);
 
-            if current_window > previous_window {
-                cnt += 1;
+            // Two consecutive windows share their middle values, so only
+            // the ones that differ need to be compared
+            if window.len() == 4 {
+                if window[3] > window[0] {
+                    cnt += 1;
+                }
+                window.pop_front();
             }
         }
 
-        cnt
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "7");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "5");
+        Ok(cnt)
     }
 }
@@ -1,21 +1,32 @@
 extern crate maplit;
-use crate::utils::input::read_file;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::input::Input;
 use maplit::hashmap;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 type SymbolList = Vec<Vec<char>>;
 
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("10", test);
-    let symbols = parse_symbols(&lines);
+pub struct Day10;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(symbols),
-            true => p2::run(symbols),
-        }
-    )
+impl Solution for Day10 {
+    type Input = SymbolList;
+
+    const DAY: u8 = 10;
+    const NAME: &'static str = "Syntax Scoring";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        parse_symbols(&lines)
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input)?.to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input)?.to_string())
+    }
 }
 
 fn get_pairs() -> (HashMap<char, char>, HashMap<char, char>) {
@@ -36,36 +47,46 @@ fn get_pairs() -> (HashMap<char, char>, HashMap<char, char>) {
     (pairs, reverse_pairs)
 }
 
+// The bracket `symbol` (at `at`, 0-based) is meant to close. It's an error
+// if there's nothing left open for it to close.
+fn pop_opening(stack: &mut VecDeque<char>, symbol: char, at: (usize, usize)) -> Result<char> {
+    stack.pop_front().ok_or_else(|| {
+        Error::parse(format!(
+            "'{}' closes a bracket that was never opened",
+            symbol
+        ))
+        .at_line(at.0 + 1)
+        .at_column(at.1 + 1)
+    })
+}
+
 mod p1 {
     extern crate maplit;
-    use super::{get_pairs, SymbolList};
+    use super::{get_pairs, pop_opening, SymbolList};
+    use crate::error::Result;
     use maplit::hashmap;
     use std::collections::{HashMap, VecDeque};
 
-    pub fn run(symbol_list: SymbolList) -> u64 {
-        let mut stack: VecDeque<char> = VecDeque::new();
+    pub fn run(symbol_list: SymbolList) -> Result<u64> {
         let (pairs, reverse_pairs) = get_pairs();
         let scores = get_scores();
         let mut score = 0;
 
-        for line in symbol_list {
-            for symbol in line {
+        for (i, line) in symbol_list.iter().enumerate() {
+            let mut stack: VecDeque<char> = VecDeque::new();
+            for (j, &symbol) in line.iter().enumerate() {
                 if pairs.contains_key(&symbol) {
                     stack.push_front(symbol);
                 } else {
-                    let popped_symbol = stack.pop_front().unwrap();
-                    match reverse_pairs.get(&symbol) {
-                        None => panic!("Huh?"),
-                        Some(matching_sym) => {
-                            if *matching_sym != popped_symbol {
-                                score += scores.get(&symbol).unwrap_or(&0);
-                            }
-                        }
+                    let popped_symbol = pop_opening(&mut stack, symbol, (i, j))?;
+                    if reverse_pairs[&symbol] != popped_symbol {
+                        score += scores.get(&symbol).unwrap_or(&0);
+                        break;
                     }
                 }
             }
         }
-        score as u64
+        Ok(score as u64)
     }
 
     fn get_scores() -> HashMap<char, i32> {
@@ -80,31 +101,27 @@ mod p1 {
 
 mod p2 {
     extern crate maplit;
-    use super::{get_pairs, SymbolList};
+    use super::{get_pairs, pop_opening, SymbolList};
+    use crate::error::{Error, Result};
     use maplit::hashmap;
     use std::collections::{HashMap, VecDeque};
 
-    pub fn run(symbol_list: SymbolList) -> u64 {
+    pub fn run(symbol_list: SymbolList) -> Result<u64> {
         let (pairs, reverse_pairs) = get_pairs();
         let score_table = get_autocomplete_score();
         let mut scores: Vec<u64> = Vec::new();
 
-        for line in symbol_list {
+        for (i, line) in symbol_list.iter().enumerate() {
             let mut corrupted = false;
             let mut stack: VecDeque<char> = VecDeque::new();
-            for symbol in line {
+            for (j, &symbol) in line.iter().enumerate() {
                 if pairs.contains_key(&symbol) {
                     stack.push_front(symbol);
                 } else {
-                    let popped_symbol = stack.pop_front().unwrap();
-                    match reverse_pairs.get(&symbol) {
-                        None => panic!("Symbol is neither opening nor closing. Bad input"),
-                        Some(matching_sym) => {
-                            if *matching_sym != popped_symbol {
-                                corrupted = true;
-                                break;
-                            }
-                        }
+                    let popped_symbol = pop_opening(&mut stack, symbol, (i, j))?;
+                    if reverse_pairs[&symbol] != popped_symbol {
+                        corrupted = true;
+                        break;
                     }
                 }
             }
@@ -112,15 +129,18 @@ mod p2 {
             if !corrupted {
                 let mut score: u64 = 0;
                 while let Some(popped_symbol) = stack.pop_front() {
-                    let matching_sym = pairs.get(&popped_symbol).unwrap();
                     score *= 5;
-                    score += score_table.get(matching_sym).unwrap_or(&0);
+                    score += score_table.get(&pairs[&popped_symbol]).unwrap_or(&0);
                 }
                 scores.push(score);
             }
         }
+
+        if scores.is_empty() {
+            return Err(Error::unsolvable("every line is corrupted"));
+        }
         scores.sort_unstable();
-        scores[scores.len() / 2]
+        Ok(scores[scores.len() / 2])
     }
 
     fn get_autocomplete_score() -> HashMap<char, u64> {
@@ -133,30 +153,20 @@ mod p2 {
     }
 }
 
-fn parse_symbols(input: &[String]) -> SymbolList {
+fn parse_symbols(input: &[String]) -> Result<SymbolList> {
     let mut lines: SymbolList = Vec::new();
 
-    for line in input {
+    for (i, line) in input.iter().enumerate() {
         let mut symbols = Vec::new();
-        for c in line.chars() {
+        for (j, c) in line.chars().enumerate() {
+            if !"()[]{}<>".contains(c) {
+                return Err(Error::parse(format!("'{}' is not a bracket", c))
+                    .at_line(i + 1)
+                    .at_column(j + 1));
+            }
             symbols.push(c);
         }
         lines.push(symbols);
     }
-    lines
-}
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "26397");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "288957");
-    }
+    Ok(lines)
 }
@@ -1,17 +1,28 @@
-use crate::utils::board::{Board, Point};
-use crate::utils::input::read_file;
-
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("11", test);
-    let board = parse_board(lines);
-
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(board),
-            true => p2::run(board),
-        }
-    )
+use crate::error::Result;
+use crate::solution::Solution;
+use crate::utils::board::{digit, Board, Neighbourhood, Point};
+use crate::utils::input::Input;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Board<Octopus>;
+
+    const DAY: u8 = 11;
+    const NAME: &'static str = "Dumbo Octopus";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        parse_board(lines)
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input).to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input).to_string())
+    }
 }
 
 pub struct Octopus {
@@ -71,7 +82,7 @@ fn flash(board: &mut Board<Octopus>, p: Point) -> i32 {
     board.get_mut(p).unwrap().flashed();
 
     let mut total_flashes = 1;
-    let neighbours = board.get_neighbours(&p, true);
+    let neighbours = board.neighbours(p, Neighbourhood::Moore);
 
     for n in neighbours {
         board.get_mut(n).unwrap().gain_energy();
@@ -82,26 +93,11 @@ fn flash(board: &mut Board<Octopus>, p: Point) -> i32 {
 }
 
 fn reset(board: &mut Board<Octopus>) {
-    let dim = board.dim();
-    for i in 0..dim.0 {
-        for j in 0..dim.1 {
-            let p = (i, j);
-            board.get_mut(p).unwrap().reset();
-        }
-    }
+    board.iter_mut().for_each(Octopus::reset);
 }
 
-fn parse_board(lines: Vec<String>) -> Board<Octopus> {
-    let mut board: Vec<Vec<Octopus>> = Vec::new();
-    for line in lines {
-        let mut row: Vec<Octopus> = Vec::new();
-        for c in line.chars() {
-            row.push(Octopus::new(c.to_digit(10).unwrap() as u8));
-        }
-        board.push(row);
-    }
-
-    Board::<Octopus>::from(board)
+fn parse_board(lines: Vec<String>) -> Result<Board<Octopus>> {
+    Board::parse_chars_with(&lines, |c| Ok(Octopus::new(digit(c)?)))
 }
 
 mod p1 {
@@ -113,19 +109,15 @@ mod p1 {
 
         for _ in 0..100 {
             let mut runtype = RunType::Increase;
-            let board_dim = board.dim();
             for _ in 0..3 {
-                for i in 0..board_dim.0 {
-                    for j in 0..board_dim.1 {
-                        let p = (i, j);
-                        match runtype {
-                            RunType::Increase => {
-                                board.get_mut(p).unwrap().gain_energy();
-                            }
-                            RunType::Flash => total_flashes += flash(&mut board, p),
-                            RunType::Reset => reset(&mut board),
+                match runtype {
+                    RunType::Increase => board.iter_mut().for_each(Octopus::gain_energy),
+                    RunType::Flash => {
+                        for p in board.positions() {
+                            total_flashes += flash(&mut board, p);
                         }
                     }
+                    RunType::Reset => reset(&mut board),
                 }
                 runtype.next();
             }
@@ -142,41 +134,22 @@ mod p2 {
     pub fn run(mut board: Board<Octopus>) -> i32 {
         let mut runtype = RunType::Increase;
         let mut step = 0;
-        let board_dim = board.dim();
         loop {
             step += 1;
             for _ in 0..3 {
-                for i in 0..board_dim.0 {
-                    for j in 0..board_dim.1 {
-                        let p = (i, j);
-                        match runtype {
-                            RunType::Increase => board.get_mut(p).unwrap().gain_energy(),
-                            RunType::Flash => {
-                                if flash(&mut board, p) == 100 {
-                                    return step;
-                                }
+                match runtype {
+                    RunType::Increase => board.iter_mut().for_each(Octopus::gain_energy),
+                    RunType::Flash => {
+                        for p in board.positions() {
+                            if flash(&mut board, p) == 100 {
+                                return step;
                             }
-                            RunType::Reset => reset(&mut board),
                         }
                     }
+                    RunType::Reset => reset(&mut board),
                 }
                 runtype.next();
             }
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "1656");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "195");
-    }
-}
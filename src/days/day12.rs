@@ -1,39 +1,52 @@
-use crate::utils::input::read_file;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::input::{pair, Input};
 use std::collections::HashMap;
 
 type Graph = HashMap<String, Vec<String>>;
 
-pub fn run(extra: bool, test: bool) -> String {
-    let input = read_file("12", test);
-    let graph = create_graph(input);
+pub struct Day12;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(graph),
-            true => p2::run(graph),
-        }
-    )
+impl Solution for Day12 {
+    type Input = Graph;
+
+    const DAY: u8 = 12;
+    const NAME: &'static str = "Passage Pathing";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        create_graph(lines)
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input).to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input).to_string())
+    }
 }
 
-fn create_graph(input: Vec<String>) -> Graph {
+fn create_graph(input: Vec<String>) -> Result<Graph> {
     let mut graph: HashMap<String, Vec<String>> = HashMap::new();
-    for line in input {
-        let split_line = line.split('-').collect::<Vec<&str>>();
+    for (i, line) in input.iter().enumerate() {
+        let (from, to) = pair(line, "-", cave, cave).map_err(|e| e.at_line(i + 1))?;
 
-        let from = split_line[0].to_string();
-        let to = split_line[1].to_string();
-
-        graph
-            .entry(from.clone())
-            .or_insert_with(Vec::new)
-            .push(to.clone());
+        graph.entry(from.clone()).or_default().push(to.clone());
         if from != "start" {
-            graph.entry(to).or_insert_with(Vec::new).push(from);
+            graph.entry(to).or_default().push(from);
         }
     }
 
-    graph
+    Ok(graph)
+}
+
+fn cave(name: &str) -> Result<String> {
+    if name.is_empty() {
+        return Err(Error::parse("a cave's name can't be empty"));
+    }
+
+    Ok(name.to_string())
 }
 
 mod p1 {
@@ -43,13 +56,13 @@ mod p1 {
     pub fn run(graph: Graph) -> i32 {
         // Only lowercase nodes go into the visited set
         let mut visited = HashSet::<String>::new();
-        bfs(&graph, &mut visited, &mut vec![], "start".to_string())
+        dfs(&graph, &mut visited, &mut vec![], "start".to_string())
     }
 
-    fn bfs(
+    fn dfs(
         graph: &Graph,
         visited: &mut HashSet<String>,
-        mut path: &mut Vec<String>,
+        path: &mut Vec<String>,
         node: String,
     ) -> i32 {
         path.push(node.clone());
@@ -66,7 +79,7 @@ mod p1 {
         } else if let Some(next_set) = graph.get(&node) {
             for next in next_set {
                 if !visited.contains(next) {
-                    sum += bfs(graph, visited, &mut path, next.to_string());
+                    sum += dfs(graph, visited, path, next.to_string());
                 }
             }
         }
@@ -87,7 +100,7 @@ mod p2 {
 
         let mut all_paths: Vec<Vec<String>> = Vec::new();
         for protected_node in protected_nodes {
-            bfs(
+            dfs(
                 &graph,
                 &mut visited,
                 &mut vec![],
@@ -102,13 +115,13 @@ mod p2 {
         all_paths.len() as i32
     }
 
-    fn bfs(
+    fn dfs(
         graph: &Graph,
         visited: &mut HashSet<String>,
-        mut path: &mut Vec<String>,
+        path: &mut Vec<String>,
         node: String,
         mut protected: (String, bool),
-        mut all_paths: &mut Vec<Vec<String>>,
+        all_paths: &mut Vec<Vec<String>>,
     ) {
         path.push(node.clone());
 
@@ -125,13 +138,13 @@ mod p2 {
 
             for next in next_set {
                 if !visited.contains(next) {
-                    bfs(
+                    dfs(
                         graph,
                         visited,
-                        &mut path,
+                        path,
                         next.to_string(),
                         protected.clone(),
-                        &mut all_paths,
+                        all_paths,
                     );
                 }
             }
@@ -144,25 +157,10 @@ mod p2 {
     fn lowercase_list(graph: &Graph) -> Vec<String> {
         let mut lowercase = vec![];
         for node in graph.keys() {
-            if node.to_lowercase() == *node && !vec!["start", "end"].contains(&&node[..]) {
+            if node.to_lowercase() == *node && !["start", "end"].contains(&&node[..]) {
                 lowercase.push(node.to_string());
             }
         }
         lowercase
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "226");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "3509");
-    }
-}
@@ -1,25 +1,35 @@
-use crate::utils::board::Point;
-use crate::utils::input::read_file;
-use regex::Regex;
-use std::collections::HashSet;
-
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("13", test);
-    let (points, folds) = parse_input(lines);
-
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(points, folds),
-            true => p2::run(points, folds),
-        }
-    )
+use crate::error::Result;
+use crate::solution::Solution;
+use crate::utils::board::Rect;
+use crate::utils::grid::{Coord, Grid};
+use crate::utils::input::{pair, parse_number, sections_exactly, Input, Pattern};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (Grid<bool>, Vec<Fold>);
+
+    const DAY: u8 = 13;
+    const NAME: &'static str = "Transparent Origami";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        parse_input(lines)
+    }
+
+    fn part1(&self, (paper, folds): Self::Input) -> Result<String> {
+        Ok(p1::run(paper, folds).to_string())
+    }
+
+    fn part2(&self, (paper, folds): Self::Input) -> Result<String> {
+        Ok(p2::run(paper, folds))
+    }
 }
 
 #[derive(Debug)]
 pub enum Fold {
-    X(usize),
-    Y(usize),
+    X(i64),
+    Y(i64),
 }
 
 /*
@@ -42,152 +52,116 @@ pub enum Fold {
  */
 
 impl Fold {
-    fn symmetrical(&self, p: &Point) -> Point {
-        let mut ret = (0, 0);
-        let f = |indx: usize, fold: usize| 2 * fold - indx;
+    // Where a dot ends up once the paper is folded
+    fn apply(&self, p: Coord) -> Coord {
+        let reflect = |indx: i64, fold: i64| if indx > fold { 2 * fold - indx } else { indx };
+        match self {
+            Fold::Y(val) => (reflect(p.0, *val), p.1),
+            Fold::X(val) => (p.0, reflect(p.1, *val)),
+        }
+    }
+
+    // What is left of the `sheet` of paper once it's folded. Unlike the
+    // dots, it keeps the rows and columns that are blank.
+    fn apply_to_sheet(&self, sheet: Rect<i64>) -> Rect<i64> {
+        let reflect = |min: i64, max: i64, fold: i64| {
+            if fold > max {
+                (min, max)
+            } else {
+                (min.min(2 * fold - max), fold - 1)
+            }
+        };
+        let (min, max) = (sheet.min, sheet.max);
         match self {
             Fold::Y(val) => {
-                ret.1 = p.1;
-                ret.0 = f(p.0, *val);
+                let (top, bottom) = reflect(min.0, max.0, *val);
+                Rect {
+                    min: (top, min.1),
+                    max: (bottom, max.1),
+                }
             }
             Fold::X(val) => {
-                ret.0 = p.0;
-                ret.1 = f(p.1, *val);
+                let (left, right) = reflect(min.1, max.1, *val);
+                Rect {
+                    min: (min.0, left),
+                    max: (max.0, right),
+                }
             }
         }
+    }
+}
 
-        ret
+// Dots that end up on top of each other merge into one
+fn fold(paper: &Grid<bool>, fold: &Fold) -> Grid<bool> {
+    let mut folded = Grid::new();
+    for (p, &dot) in paper.iter() {
+        folded.insert(fold.apply(p), dot);
     }
+
+    folded
 }
 
-fn parse_input(lines: Vec<String>) -> (HashSet<Point>, Vec<Fold>) {
-    let mut points_section = true;
-    let mut points: HashSet<Point> = HashSet::new();
+fn parse_input(lines: Vec<String>) -> Result<(Grid<bool>, Vec<Fold>)> {
+    let [dots, instructions] = sections_exactly(&lines)?;
+    let mut paper: Grid<bool> = Grid::new();
     let mut folds: Vec<Fold> = Vec::new();
-    let regex = Regex::new(r"^.*(?P<axis>.)=(?P<value>\d+)$").unwrap();
-    for line in lines {
-        if line.is_empty() {
-            points_section = false;
-            continue;
-        }
 
-        if points_section {
-            let split: Vec<&str> = line.split(',').collect();
-            points.insert((
-                split[1].parse::<usize>().unwrap(),
-                split[0].parse::<usize>().unwrap(),
-            ));
-        } else if let Some(x) = regex.captures(&line) {
-            let val = x.name("value").unwrap().as_str().parse::<usize>().unwrap();
-            let axis = x.name("axis").unwrap().as_str();
-            let fold = match axis {
-                "x" => Fold::X(val),
-                "y" => Fold::Y(val),
-                &_ => unreachable!(),
-            };
-
-            folds.push(fold)
-        }
+    for (n, line) in dots.numbered() {
+        let (x, y) = pair(line, ",", parse_number, parse_number).map_err(|e| e.at_line(n))?;
+        paper.insert((y, x), true);
+    }
+
+    let pattern = Pattern::new(
+        r"^fold along (?P<axis>[xy])=(?P<value>\d+)$",
+        "a fold of the form 'fold along x=5'",
+    );
+    for (n, line) in instructions.numbered() {
+        let fold = pattern
+            .captures(line)
+            .and_then(|caps| {
+                let val = caps.get("value", parse_number)?;
+                Ok(match caps.str("axis")? {
+                    "x" => Fold::X(val),
+                    _ => Fold::Y(val),
+                })
+            })
+            .map_err(|e| e.at_line(n))?;
+
+        folds.push(fold)
     }
 
-    (points, folds)
+    Ok((paper, folds))
 }
 
 mod p1 {
-    use super::Fold;
-    use crate::utils::board::Point;
-    use std::collections::HashSet;
-
-    pub fn run(points: HashSet<Point>, folds: Vec<Fold>) -> i32 {
-        let mut cnt = 0;
-
-        let fold = &folds[0];
-
-        for point in points.iter() {
-            match fold {
-                Fold::X(val) => {
-                    if point.1 < *val {
-                        cnt += 1;
-                    } else {
-                        let sym = fold.symmetrical(point);
-                        // Only count symmetrical points if they are not contained in the pointset
-                        if sym.1 < *val && !points.contains(&sym) {
-                            // Since only one fold occurs in p1, we don't need to add the symmetrical
-                            // point to the original ones
-                            cnt += 1;
-                        }
-                    }
-                }
-                Fold::Y(val) => {
-                    if point.0 < *val {
-                        cnt += 1;
-                    } else {
-                        let sym = fold.symmetrical(point);
-                        // Only count symmetrical points if they are not contained in the pointset
-                        if !points.contains(&sym) {
-                            // Since only one fold occurs in p1, we don't need to add the symmetrical
-                            // point to the original ones
-                            cnt += 1;
-                        }
-                    }
-                }
-            }
-        }
-        cnt
+    use super::{fold, Fold};
+    use crate::utils::grid::Grid;
+
+    pub fn run(paper: Grid<bool>, folds: Vec<Fold>) -> usize {
+        fold(&paper, &folds[0]).len()
     }
 }
 
 mod p2 {
-    use super::Fold;
-    use crate::utils::board::{Board, Point};
-    use std::collections::HashSet;
-
-    pub fn run(points: HashSet<Point>, folds: Vec<Fold>) -> i32 {
-        let mut curr_points: HashSet<Point> = points;
-        for fold in folds {
-            let mut next_points: HashSet<Point> = HashSet::new();
-            for point in curr_points.iter() {
-                match fold {
-                    Fold::Y(val) => {
-                        if point.0 < val {
-                            next_points.insert(*point);
-                        } else {
-                            let sym = fold.symmetrical(point);
-                            next_points.insert(sym);
-                        }
-                    }
-                    Fold::X(val) => {
-                        if point.1 < val {
-                            next_points.insert(*point);
-                        } else {
-                            let sym = fold.symmetrical(point);
-                            next_points.insert(sym);
-                        }
-                    }
-                }
-            }
-            curr_points = next_points.clone();
+    use super::{fold, Fold};
+    use crate::utils::board::Rect;
+    use crate::utils::grid::Grid;
+
+    // The answer is whatever the dots spell out
+    pub fn run(mut paper: Grid<bool>, folds: Vec<Fold>) -> String {
+        // The sheet starts at (0, 0), however far from it the first dot is
+        let mut sheet = Rect {
+            min: (0, 0),
+            max: paper.bounds().map_or((0, 0), |bounds| bounds.max),
+        };
+        for f in folds {
+            paper = fold(&paper, &f);
+            sheet = f.apply_to_sheet(sheet);
         }
 
-        let board: Board<char> =
-            Board::from_points(&(curr_points.into_iter().collect::<Vec<Point>>()), '.', '#');
-        for row in board.expose() {
-            println!("{}", row.iter().collect::<String>());
-        }
-
-        0
+        paper
+            .to_board_within(sheet, false)
+            .render(|&dot| if dot { '#' } else { '.' })
+            .to_text()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "17")
-    }
-
-    // Cannot write tests for p2 since it
-    // depends on reading the 2D array in the output
-}
@@ -1,18 +1,33 @@
-use crate::utils::input::read_file;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::input::{arrow_pair, sections_exactly, Input, Section};
 use std::collections::HashMap;
+use std::convert::TryInto;
 
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("14", test);
-    let polymer = Polymer::from_string(&lines[0]);
-    let rules = parse_rules(&lines[2..]);
+pub struct Day14;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(polymer, rules),
-            true => p2::run(polymer, rules),
-        }
-    )
+impl Solution for Day14 {
+    type Input = (Polymer, Rules);
+
+    const DAY: u8 = 14;
+    const NAME: &'static str = "Extended Polymerization";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        let [template, rules] = sections_exactly(&lines)?;
+        let polymer =
+            Polymer::from_string(template.single_line()?).map_err(|e| e.at_line(template.start))?;
+
+        Ok((polymer, parse_rules(&rules)?))
+    }
+
+    fn part1(&self, (polymer, rules): Self::Input) -> Result<String> {
+        Ok(p1::run(polymer, rules).to_string())
+    }
+
+    fn part2(&self, (polymer, rules): Self::Input) -> Result<String> {
+        Ok(p2::run(polymer, rules).to_string())
+    }
 }
 
 type Rules = HashMap<(u8, u8), u8>;
@@ -24,7 +39,11 @@ pub struct Polymer {
 }
 
 impl Polymer {
-    pub fn from_string(input: &str) -> Self {
+    pub fn from_string(input: &str) -> Result<Self> {
+        if input.is_empty() {
+            return Err(Error::parse("the polymer template is empty"));
+        }
+
         let mut segments: HashMap<(u8, u8), usize> = HashMap::new();
         let bytes = input.as_bytes();
         bytes
@@ -33,11 +52,11 @@ impl Polymer {
         let first = bytes[0];
         let last = bytes[bytes.len() - 1];
 
-        Polymer {
+        Ok(Polymer {
             segments,
             first,
             last,
-        }
+        })
     }
 
     pub fn apply_rules(&mut self, rules: &Rules) {
@@ -78,16 +97,23 @@ impl Polymer {
     }
 }
 
-fn parse_rules(lines: &[String]) -> Rules {
+fn parse_rules(rules: &Section) -> Result<Rules> {
     let mut pairs: Rules = HashMap::new();
 
-    for line in lines {
-        let parts = line.split(" -> ").collect::<Vec<&str>>();
-        let (left, right) = (parts[0].as_bytes(), parts[1].as_bytes()[0]);
-        pairs.insert((left[0], left[1]), right);
+    for (n, line) in rules.numbered() {
+        let ([left, right], [inserted]) =
+            arrow_pair(line, elements, elements).map_err(|e| e.at_line(n))?;
+        pairs.insert((left, right), inserted);
     }
 
-    pairs
+    Ok(pairs)
+}
+
+// The `N` elements of one side of a rule, e.g. `AB` of `AB -> C`
+fn elements<const N: usize>(side: &str) -> Result<[u8; N]> {
+    side.as_bytes()
+        .try_into()
+        .map_err(|_| Error::parse(format!("expected {} element(s), found '{}'", N, side)))
 }
 
 mod p1 {
@@ -111,18 +137,3 @@ mod p2 {
         poly.score()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "1588");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "2188189693529")
-    }
-}
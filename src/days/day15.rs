@@ -1,196 +1,100 @@
-use crate::utils::board::{Board, Point};
-use crate::utils::input::read_file;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("15", test);
-    let board: Board<u32> = Board::from(parse_lines(lines));
-
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(board),
-            true => p2::run(board),
-        }
-    )
-}
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::board::{digit, Board, Neighbourhood, Neighbours, Point};
+use crate::utils::input::Input;
+use crate::utils::search::{dijkstra_dense, Found};
 
-fn parse_lines(lines: Vec<String>) -> Vec<Vec<u32>> {
-    let mut ret: Vec<Vec<u32>> = Vec::new();
-    lines.into_iter().for_each(|line| {
-        ret.push(line.chars().map(|c| c.to_digit(10).unwrap()).collect());
-    });
+// How many times the map is repeated in each direction in part 2, unless
+// set with `--param tiles=N`
+const TILES: usize = 5;
 
-    ret
-}
+pub struct Day15;
 
-#[derive(Eq, PartialEq, Copy, Clone)]
-pub struct Element {
-    p: Point,
-    v: u32,
-}
+impl Solution for Day15 {
+    type Input = (Board<u32>, usize);
+
+    const DAY: u8 = 15;
+    const NAME: &'static str = "Chiton";
+    const PARAMS: &'static [&'static str] = &["tiles"];
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let tiles = input.param("tiles")?.unwrap_or(TILES);
+        if tiles == 0 {
+            return Err(Error::param("tiles", "there must be at least one tile"));
+        }
 
-impl Ord for Element {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.v.cmp(&self.v)
+        let lines = input.lines()?;
+        Ok((parse_lines(lines)?, tiles))
     }
-}
 
-impl PartialOrd for Element {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn part1(&self, (board, _): Self::Input) -> Result<String> {
+        Ok(p1::run(board).to_string())
     }
-}
 
-fn dijkstra(board: Board<u32>) -> u32 {
-    let mut visited: Board<u32> = Board::with_defaults(u32::MAX, board.dim());
-    let mut to_visit: BinaryHeap<Element> = BinaryHeap::new();
-    let mut parents: HashMap<Point, Point> = HashMap::new();
-
-    to_visit.push(Element {
-        p: (0, 0),
-        v: *board.get((0, 0)).unwrap(),
-    });
-
-    while let Some(e) = to_visit.pop() {
-        let neighbours = board.get_neighbours(&e.p, false);
-        *visited.get_mut(e.p).unwrap() = e.v;
-        for n in neighbours {
-            let curr_score = e.v + *board.get(n).unwrap();
-            // If not in visited
-            let visited_neighbour_score = visited.get_mut(n).unwrap();
-            let &neighbour_score = board.get(n).unwrap();
-            let cumulative_score = curr_score + neighbour_score;
-            if *visited_neighbour_score == u32::MAX {
-                to_visit.push(Element {
-                    p: n,
-                    v: cumulative_score,
-                });
-
-                parents.insert(n, e.p);
-                *visited.get_mut(n).unwrap() = neighbour_score;
-            } else if cumulative_score < *visited_neighbour_score {
-                *visited_neighbour_score = cumulative_score;
-            }
-        }
+    fn part2(&self, (board, tiles): Self::Input) -> Result<String> {
+        Ok(p2::run(board, tiles).to_string())
     }
+}
 
-    let dim = board.dim();
-    let mut curr = (dim.0 - 1, dim.1 - 1);
-    let mut sum = *board.get(curr).unwrap();
-    while let Some(p) = parents.get(&curr) {
-        if p == &(0, 0) {
-            break;
-        }
-        sum += *board.get(*p).unwrap();
-        curr = *p;
+fn parse_lines(lines: Vec<String>) -> Result<Board<u32>> {
+    let board = Board::parse_chars_with(&lines, |c| match digit(c)? {
+        risk @ 1..=9 => Ok(risk as u32),
+        risk => Err(Error::parse(format!(
+            "a risk level is between 1 and 9, found {}",
+            risk
+        ))),
+    })?;
+    if board.dim() == (0, 0) {
+        return Err(Error::parse("the input is empty"));
     }
 
-    sum
+    Ok(board)
+}
+
+// The path of lowest total risk from the top left to the bottom right of a
+// map, given by its dimensions and the risk of every position. The risk of
+// the top left position doesn't count, since it's never entered.
+pub fn safest_path(dim: (usize, usize), risk: impl Fn(Point) -> u32) -> Found<Point, u32> {
+    let goal = (dim.0 - 1, dim.1 - 1);
+
+    dijkstra_dense(
+        dim,
+        vec![(0, 0)],
+        |&p| Neighbours::around(p, dim, Neighbourhood::VonNeumann),
+        |_, &to| risk(to),
+        |&p| p == goal,
+    )
+    .expect("every position of a map can be reached")
 }
 
 mod p1 {
-    use super::dijkstra;
+    use super::safest_path;
     use crate::utils::board::Board;
 
     pub fn run(board: Board<u32>) -> u32 {
-        dijkstra(board)
+        safest_path(board.dim(), |p| *board.get(p).unwrap()).cost
     }
 }
 
 mod p2 {
-    use super::dijkstra;
+    use super::safest_path;
     use crate::utils::board::Board;
 
-    pub fn run(board: Board<u32>) -> u32 {
-        dijkstra(enlarge(&board))
-    }
-
-    // Enlarge the board 5 times in each direction (25 times overall)
-    fn enlarge(board: &Board<u32>) -> Board<u32> {
-        let dim = board.dim();
-        let mut new_boards: Vec<Board<u32>> = vec![Board::from(board.expose().clone())];
-
-        /* We initially get all possible variants of the board.
-         * Since copying the board to the left or the bottom is the (increased by 1),
-         * while copying diagonally increases values by 2
-         * e.g.
-         * 1 1 ...
-         * 1 2 2
-         *.. 2 3
-         *
-         * We can only ever have 9 distinct versions of the board (1 original + 8 new).
-         */
-        for inc in 1..9 {
-            let mut new_board = Board::<u32>::with_defaults(0, dim);
-            for i in 0..dim.0 {
-                for j in 0..dim.1 {
-                    let p = (i, j);
-                    let old_val = board.get(p).unwrap();
-                    let mut new_val = (old_val + inc) % 9;
-                    if new_val == 0 {
-                        new_val = 9;
-                    }
-                    *new_board.get_mut(p).unwrap() = new_val;
-                }
-            }
-            new_boards.push(new_board);
-        }
-
-        arrange_boards(new_boards)
-    }
-
-    /* Given a vector of the 9 distinct boards, arranges and flattens them into one
-     * The arrangement is as follows: (each board is denoted by its index in the vector)
+    /* The full map is the board repeated `tiles` times in each direction.
+     * Every copy to the right or downwards has its risks increased by 1,
+     * wrapping from 9 back to 1, e.g. (by how much each copy is increased)
      * 0 1 2 3 4
      * 1 2 3 4 5
-     * 2 3 4 5 6
-     * 3 4 5 6 7
-     * 4 5 6 7 8
+     * ...
+     * The copies are never made, risks are worked out as they are needed,
+     * and the search only keeps the lowest risk of every position. That's
+     * what lets it go to 100 tiles (10^8 positions for the real input).
      */
-    fn arrange_boards(boards: Vec<Board<u32>>) -> Board<u32> {
-        let mut flat_board: Vec<Vec<u32>> = Vec::new();
-
-        let dim = boards[0].dim();
-        // We'll be copying the boards 5 times downwards
-        for downwards_index in 0..5 {
-            for board_row in 0..dim.0 {
-                flat_board.push(Vec::<u32>::new());
-                // This is indeed only used to loop
-                // over the boards, however the order, while
-                // linear, is somewhat erradic. It would be
-                // interesting to check how this would be done with
-                // iterators. Maybe some other time
-                #[allow(clippy::needless_range_loop)]
-                // Each new row of boards is one time larger than the previous one
-                // e.g.
-                // 1 2 3 4 5
-                // 2 3 4 5 6
-                // ...
-                // As such, we need increase the 5 indices of the boards we use by `board_row`
-                for board_index in downwards_index..(downwards_index + 5) {
-                    let mut acc_row = boards[board_index].get_row(board_row).unwrap().clone();
-                    flat_board.last_mut().unwrap().append(&mut acc_row);
-                }
-            }
-        }
-
-        Board::from(flat_board)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "40")
-    }
+    pub fn run(board: Board<u32>, tiles: usize) -> u32 {
+        let map = board.tiled(tiles, tiles, |tile_x, tile_y, &risk| {
+            ((risk as usize - 1 + tile_x + tile_y) % 9 + 1) as u32
+        });
 
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "315")
+        safest_path(map.dim(), |p| map.get(p).unwrap()).cost
     }
 }
@@ -1,46 +1,45 @@
+use crate::error::Result;
 use crate::parsing::Parser;
-use crate::utils::input::read_file;
-
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("16", test);
-    let packet = Parser::from(&lines[0][..]);
-
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(packet),
-            true => p2::run(packet),
-        }
-    )
+use crate::solution::Solution;
+use crate::utils::input::{first_line, Input};
+use std::convert::TryFrom;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Parser;
+
+    const DAY: u8 = 16;
+    const NAME: &'static str = "Packet Decoder";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        Parser::try_from(first_line(&lines)?).map_err(|e| e.at_line(1))
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input)?.to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input)?.to_string())
+    }
 }
 
 mod p1 {
+    use crate::error::Result;
     use crate::parsing::Parser;
 
-    pub fn run(mut parser: Parser) -> isize {
+    pub fn run(mut parser: Parser) -> Result<isize> {
         parser.versions()
     }
 }
 
 mod p2 {
+    use crate::error::Result;
     use crate::parsing::Parser;
 
-    pub fn run(mut packet: Parser) -> isize {
+    pub fn run(mut packet: Parser) -> Result<isize> {
         packet.execute()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "31");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "54");
-    }
-}
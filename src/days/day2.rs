@@ -1,16 +1,27 @@
-use crate::utils::input::read_file;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::input::{column_of, fields, parse_at, parse_number, Input, Stream};
 
-pub fn run(extra: bool, test: bool) -> String {
-    let input = read_file("2", test);
-    let directions = Direction::parse(input);
+pub struct Day2;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(directions),
-            true => p2::run(directions),
-        }
-    )
+impl Solution for Day2 {
+    type Input = Directions;
+
+    const DAY: u8 = 2;
+    const NAME: &'static str = "Dive!";
+    const STREAMS: bool = true;
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        Ok(Directions(input.stream()))
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input)?.to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input)?.to_string())
+    }
 }
 
 pub enum Direction {
@@ -19,24 +30,34 @@ pub enum Direction {
     Down(u32),
 }
 
-impl Direction {
-    fn parse(input: Vec<String>) -> Vec<Self> {
-        let mut directions: Vec<Self> = Vec::new();
-
-        for line in input {
-            let split_line: Vec<&str> = line.split_whitespace().collect();
-            let dir = split_line[0];
-            let distance = split_line[1].parse::<u32>().unwrap();
-
-            directions.push(match dir {
-                "up" => Self::Up(distance),
-                "forward" => Self::Forward(distance),
-                "down" => Self::Down(distance),
-                &_ => panic!("Unknown direction: {}", dir),
-            });
-        }
+// The directions, parsed one at a time as they are read
+pub struct Directions(Stream);
+
+impl Iterator for Directions {
+    type Item = Result<Direction>;
 
-        directions
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|line| {
+            let (n, line) = line?;
+            Direction::parse_line(&line).map_err(|e| e.at_line(n))
+        })
+    }
+}
+
+impl Direction {
+    fn parse_line(line: &str) -> Result<Self> {
+        let [dir, distance] = fields(line)?;
+        let distance = parse_at(line, distance, parse_number::<u32>)?;
+
+        Ok(match dir {
+            "up" => Self::Up(distance),
+            "forward" => Self::Forward(distance),
+            "down" => Self::Down(distance),
+            &_ => {
+                return Err(Error::parse(format!("Unknown direction: {}", dir))
+                    .at_column(column_of(line, dir)))
+            }
+        })
     }
 }
 
@@ -58,14 +79,15 @@ impl Position {
 
 mod p1 {
     use super::{Direction, Position};
+    use crate::error::Result;
 
     // Calculate final distance after following
     // directions (up, down, forward)
-    pub fn run(directions: Vec<Direction>) -> i32 {
+    pub fn run(directions: impl Iterator<Item = Result<Direction>>) -> Result<i32> {
         let mut position = Position::new();
 
         for direction in directions {
-            match direction {
+            match direction? {
                 Direction::Up(distance) => {
                     position.depth -= distance as i32;
                 }
@@ -78,20 +100,21 @@ mod p1 {
             }
         }
 
-        position.hor * position.depth
+        Ok(position.hor * position.depth)
     }
 }
 
 mod p2 {
     use super::{Direction, Position};
+    use crate::error::Result;
 
     // Calculate final distance after following
     // directions (up -- main, down -- aim, forward -- horizontal & vertical position)
-    pub fn run(directions: Vec<Direction>) -> i32 {
+    pub fn run(directions: impl Iterator<Item = Result<Direction>>) -> Result<i32> {
         let mut position = Position::new();
 
         for direction in directions {
-            match direction {
+            match direction? {
                 Direction::Up(distance) => {
                     position.aim -= distance as i32;
                 }
@@ -105,21 +128,6 @@ mod p2 {
             }
         }
 
-        position.hor * position.depth
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "150");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "900");
+        Ok(position.hor * position.depth)
     }
 }
@@ -1,18 +1,32 @@
-use crate::utils::input::read_file;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::input::Input;
 
 type Binset = Vec<Bin>;
 
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("3", test);
-    let binary = Bin::from_input(lines);
+// Both parts multiply two of the numbers, which has to fit in an isize
+const MAX_BITS: usize = 31;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(binary),
-            true => p2::run(binary),
-        }
-    )
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Binset;
+
+    const DAY: u8 = 3;
+    const NAME: &'static str = "Binary Diagnostic";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        Bin::from_input(lines)
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input).to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input)?.to_string())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -21,16 +35,53 @@ pub struct Bin {
 }
 
 impl Bin {
-    fn from_input(input: Vec<String>) -> Binset {
+    fn from_input(input: Vec<String>) -> Result<Binset> {
         let mut bitset = Vec::new();
-        for line in input {
-            let digits = line.chars().map(|s| s.to_string());
-            bitset.push(Self {
-                bits: digits.map(|d| d == "1").collect(),
-            });
+        for (i, line) in input.iter().enumerate() {
+            let mut bits = Vec::new();
+            for (j, c) in line.chars().enumerate() {
+                bits.push(match c {
+                    '1' => true,
+                    '0' => false,
+                    _ => {
+                        return Err(Error::parse(format!("'{}' is not a binary digit", c))
+                            .at_line(i + 1)
+                            .at_column(j + 1))
+                    }
+                });
+            }
+
+            if bits.is_empty() {
+                return Err(Error::parse("expected a binary number, found nothing").at_line(i + 1));
+            }
+            if bits.len() > MAX_BITS {
+                return Err(Error::parse(format!(
+                    "expected at most {} bits, found {}",
+                    MAX_BITS,
+                    bits.len()
+                ))
+                .at_line(i + 1)
+                .at_column(MAX_BITS + 1));
+            }
+
+            // Every bit position is compared across all numbers
+            if bitset.first().is_some_and(|b: &Bin| b.len() != bits.len()) {
+                return Err(Error::parse(format!(
+                    "expected {} bits, found {}",
+                    bitset[0].len(),
+                    bits.len()
+                ))
+                .at_line(i + 1));
+            }
+
+            bitset.push(Self { bits });
         }
 
-        bitset
+        if bitset.is_empty() {
+            return Err(Error::parse("the input is empty"));
+        }
+
+        Ok(bitset)
     }
 
     fn bit_at(&self, indx: usize) -> bool {
@@ -38,20 +89,9 @@ impl Bin {
     }
 
     fn to_decimal(&self) -> isize {
-        let res = self
-            .bits
+        self.bits
             .iter()
-            .map(|&b| {
-                match b {
-                    true => "1",
-                    false => "0",
-                }
-                .to_string()
-            })
-            .collect::<Vec<String>>()
-            .join("");
-
-        isize::from_str_radix(&res, 2).unwrap()
+            .fold(0, |decimal, &bit| decimal << 1 | bit as isize)
     }
 
     fn len(&self) -> usize {
@@ -111,9 +151,10 @@ mod p1 {
 
 mod p2 {
     use super::{count_bits, Bin, Binset};
+    use crate::error::{Error, Result};
 
-    pub fn run(bitset: Binset) -> isize {
-        solve_1s(&bitset) * solve_0s(&bitset)
+    pub fn run(bitset: Binset) -> Result<isize> {
+        Ok(solve_1s(&bitset)? * solve_0s(&bitset)?)
     }
 
     /*
@@ -123,7 +164,7 @@ mod p2 {
       refactoring to make Binset an actual struct would make sense here
     */
     #[allow(clippy::ptr_arg)]
-    fn solve_0s(binary: &Binset) -> isize {
+    fn solve_0s(binary: &Binset) -> Result<isize> {
         let byte_size = binary[0].len();
         let mut prev_binary = binary.clone();
         let mut answer = Vec::new();
@@ -147,10 +188,13 @@ mod p2 {
         }
 
         if answer.len() != 1 {
-            panic!("Invalid binary: {:?}", answer);
+            return Err(Error::unsolvable(format!(
+                "{} numbers are left instead of one",
+                answer.len()
+            )));
         }
 
-        answer[0].to_decimal()
+        Ok(answer[0].to_decimal())
     }
 
     /*
@@ -160,7 +204,7 @@ mod p2 {
       refactoring to make Binset an actual struct would make sense here
     */
     #[allow(clippy::ptr_arg)]
-    fn solve_1s(binary: &Binset) -> isize {
+    fn solve_1s(binary: &Binset) -> Result<isize> {
         let byte_size = binary[0].len();
         let mut prev_binary = binary.clone();
         let mut answer = Vec::new();
@@ -182,24 +226,12 @@ mod p2 {
         }
 
         if answer.len() != 1 {
-            panic!("Invalid binary: {:?}", answer);
+            return Err(Error::unsolvable(format!(
+                "{} numbers are left instead of one",
+                answer.len()
+            )));
         }
 
-        answer[0].to_decimal()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "198");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "230");
+        Ok(answer[0].to_decimal())
     }
 }
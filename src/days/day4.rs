@@ -1,39 +1,49 @@
+use crate::error::{Error, Result};
+use crate::solution::Solution;
 use crate::utils::board::{Board, Point};
-use crate::utils::input::read_file;
+use crate::utils::input::{column_of, parse_number, sections, Input, Section};
 
 const BOARD_LENGTH: usize = 5;
 
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("4", test);
+pub struct Day4;
 
-    let (inputs, boards) = parse_lines(lines);
+impl Solution for Day4 {
+    type Input = (Vec<u32>, Vec<BingoBoard>);
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(inputs, boards),
-            true => p2::run(inputs, boards),
-        }
-    )
+    const DAY: u8 = 4;
+    const NAME: &'static str = "Giant Squid";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        parse_lines(lines)
+    }
+
+    fn part1(&self, (inputs, boards): Self::Input) -> Result<String> {
+        Ok(p1::run(inputs, boards).to_string())
+    }
+
+    fn part2(&self, (inputs, boards): Self::Input) -> Result<String> {
+        Ok(p2::run(inputs, boards)?.to_string())
+    }
 }
 
 pub struct BingoBoard {
-    board: Board<(String, bool)>,
+    board: Board<(u32, bool)>,
     indx: usize,
 }
 
 impl BingoBoard {
-    fn new_draw(&mut self, draw: &str) -> bool {
-        let dim = self.board.dim();
-        for i in 0..dim.0 {
-            for j in 0..dim.1 {
-                let p = (i, j);
-                if self.board.get(p).unwrap().0 == draw {
-                    return self.mark(p);
-                }
-            }
+    fn new_draw(&mut self, draw: u32) -> bool {
+        let drawn = self
+            .board
+            .enumerate()
+            .find(|(_, cell)| cell.0 == draw)
+            .map(|(p, _)| p);
+
+        match drawn {
+            Some(p) => self.mark(p),
+            None => false,
         }
-        false
     }
 
     fn mark(&mut self, p: Point) -> bool {
@@ -41,84 +51,92 @@ impl BingoBoard {
         self.check_win(p)
     }
 
+    // Only the row and column of the newly marked point can have been completed
     fn check_win(&self, p: Point) -> bool {
-        let hor_win = self.board.expose()[p.0].iter().all(|(_, b)| *b);
-        let mut ver_win = false;
-        let mut cnt = 0;
-        let dim = self.board.dim();
-        for _ in 0..dim.0 {
-            if self.board.get(p).unwrap().1 {
-                cnt += 1;
-            }
-            ver_win = cnt == dim.1;
-            if ver_win {
-                break;
-            }
-        }
+        let hor_win = self.board.row(p.0).unwrap().iter().all(|(_, b)| *b);
+        let ver_win = self.board.column(p.1).unwrap().all(|(_, b)| *b);
 
         hor_win || ver_win
     }
 
-    fn score(&self, latest: &str) -> i32 {
-        let mut score = 0;
-        for row in self.board.expose().iter() {
-            for col in row {
-                if !col.1 {
-                    score += col.0.parse::<i32>().unwrap();
-                }
-            }
-        }
+    fn score(&self, latest: u32) -> i32 {
+        let unmarked: i32 = self
+            .board
+            .iter()
+            .filter(|(_, marked)| !marked)
+            .map(|(num, _)| *num as i32)
+            .sum();
 
-        score * latest.parse::<i32>().unwrap()
+        unmarked * latest as i32
     }
 
-    fn from_lines(lines: &[String], indx: usize) -> Self {
-        let mut b = Vec::<Vec<(String, bool)>>::new();
+    fn from_section(section: &Section, indx: usize) -> Result<Self> {
+        if section.lines.len() != BOARD_LENGTH {
+            return Err(Error::parse(format!(
+                "expected a board of {} rows, found {}",
+                BOARD_LENGTH,
+                section.lines.len()
+            ))
+            .at_line(section.start));
+        }
+
+        let mut b = Vec::<Vec<(u32, bool)>>::new();
 
-        for row in lines {
-            let data: Vec<(String, bool)> = row
-                .split_whitespace()
-                .into_iter()
-                .map(|e| (e.to_string(), false))
-                .collect();
+        for (line, row) in section.numbered() {
+            let mut data: Vec<(u32, bool)> = Vec::new();
+            for e in row.split_whitespace() {
+                let num = parse_number(e)
+                    .map_err(|err| err.at_line(line).at_column(column_of(row, e)))?;
+                data.push((num, false));
+            }
+
+            if data.len() != BOARD_LENGTH {
+                return Err(Error::parse(format!(
+                    "expected {} numbers in a board row, found {}",
+                    BOARD_LENGTH,
+                    data.len()
+                ))
+                .at_line(line));
+            }
             b.push(data);
         }
 
-        Self {
-            board: Board::<(String, bool)>::from(b),
+        Ok(Self {
+            board: Board::<(u32, bool)>::from(b),
             indx,
-        }
+        })
     }
 }
 
-fn parse_lines(lines: Vec<String>) -> (Vec<String>, Vec<BingoBoard>) {
-    let inputs: Vec<String> = lines[0].split(',').map(|s| s.to_string()).collect();
-
-    let mut boards = Vec::<BingoBoard>::new();
-    let mut cnt = 2;
-    let mut indx = 0;
-
-    loop {
-        boards.push(BingoBoard::from_lines(
-            &lines[cnt..cnt + BOARD_LENGTH],
-            indx,
-        ));
-        indx += 1;
-        cnt += BOARD_LENGTH + 1; // 1 blank line
-
-        if cnt >= lines.len() {
-            break;
-        }
+fn parse_lines(lines: Vec<String>) -> Result<(Vec<u32>, Vec<BingoBoard>)> {
+    let sections = sections(&lines);
+    let (draws, boards) = sections
+        .split_first()
+        .ok_or_else(|| Error::parse("the input is empty"))?;
+
+    let line = draws.single_line()?;
+    let mut inputs: Vec<u32> = Vec::new();
+    for draw in line.split(',') {
+        inputs.push(
+            parse_number(draw)
+                .map_err(|e| e.at_line(draws.start).at_column(column_of(line, draw)))?,
+        );
     }
 
-    (inputs, boards)
+    let boards = boards
+        .iter()
+        .enumerate()
+        .map(|(indx, section)| BingoBoard::from_section(section, indx))
+        .collect::<Result<Vec<BingoBoard>>>()?;
+
+    Ok((inputs, boards))
 }
 
 mod p1 {
     use super::BingoBoard;
 
-    pub fn run(inputs: Vec<String>, mut boards: Vec<BingoBoard>) -> i32 {
-        for input in inputs.iter() {
+    pub fn run(inputs: Vec<u32>, mut boards: Vec<BingoBoard>) -> i32 {
+        for input in inputs {
             for board in &mut boards.iter_mut() {
                 if board.new_draw(input) {
                     return board.score(input);
@@ -132,8 +150,9 @@ mod p1 {
 
 mod p2 {
     use super::BingoBoard;
+    use crate::error::{Error, Result};
 
-    pub fn run(inputs: Vec<String>, mut boards: Vec<BingoBoard>) -> i32 {
+    pub fn run(inputs: Vec<u32>, mut boards: Vec<BingoBoard>) -> Result<i32> {
         let mut scores: Vec<(i32, usize)> = Vec::new();
         for number in inputs {
             for board in boards.iter_mut() {
@@ -144,14 +163,16 @@ mod p2 {
                 {
                     continue;
                 }
-                if board.new_draw(&number) {
-                    let score = board.score(&number);
+                if board.new_draw(number) {
+                    let score = board.score(number);
                     scores.push((score, board.indx));
                 }
             }
         }
 
-        1
+        Err(Error::unsolved(
+            "the score of the last board to win isn't worked out",
+        ))
     }
 }
 
@@ -1,218 +1,93 @@
-use crate::utils::board::Point;
-use crate::utils::input::read_file;
-
-type Pointpair = (Point, Point);
-type Pointset = Vec<(Point, Point)>;
-
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("5", test);
-    let points: Pointset = from_lines(lines);
-    let oceanfloor = Oceanfloor::new(&points);
-
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(oceanfloor, points),
-            true => p2::run(oceanfloor, points),
-        }
-    )
-}
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::grid::{Coord, Grid};
+use crate::utils::input::{arrow_pair, pair, parse_number, Input};
 
-fn from_lines(lines: Vec<String>) -> Pointset {
-    let mut points = Vec::new();
-    for line in lines {
-        points.push(parse_pair(&line));
-    }
+type Pointpair = (Coord, Coord);
+type Pointset = Vec<Pointpair>;
 
-    points
-}
+pub struct Day5;
 
-fn parse_pair(line: &str) -> Pointpair {
-    let points = line
-        .split(" -> ")
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
-    let p1 = parse(&points, 0);
-    let p2 = parse(&points, 1);
+impl Solution for Day5 {
+    type Input = Pointset;
 
-    (p1, p2)
-}
+    const DAY: u8 = 5;
+    const NAME: &'static str = "Hydrothermal Venture";
 
-fn parse(points: &[String], indx: usize) -> Point {
-    let coords: Vec<usize> = points[indx]
-        .split(',')
-        .map(|s| s.parse::<usize>().unwrap())
-        .collect();
-
-    (coords[0], coords[1])
-}
-
-fn interval(p1: Point, p2: Point, diagonals: bool) -> Vec<Point> {
-    if p1.0 == p2.0 {
-        let mut points = Vec::new();
-        let (mut x, mut y) = (p1.1, p2.1);
-
-        if x > y {
-            std::mem::swap(&mut x, &mut y);
-        }
-
-        for i in x..=y {
-            points.push((p1.1, i));
-        }
-        return points;
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        from_lines(lines)
     }
 
-    if p1.1 == p2.1 {
-        let mut points = Vec::new();
-        let (mut x, mut y) = (p1.0, p2.0);
-
-        if x > y {
-            std::mem::swap(&mut x, &mut y);
-        }
-        for i in x..=y {
-            points.push((i, p1.1));
-        }
-        return points;
+    fn part1(&self, points: Self::Input) -> Result<String> {
+        Ok(p1::run(points).to_string())
     }
 
-    if diagonals {
-        let mut points = vec![p1, p2];
-
-        let mut i = p1.0;
-        let mut j = p1.1;
-
-        let down_x = p1.0 > p2.0;
-        let down_y = p1.1 > p2.1;
-
-        while (i != p2.0) && (j != p2.1) {
-            points.push((i, j));
-            if down_x {
-                i -= 1;
-            } else {
-                i += 1;
-            }
-
-            if down_y {
-                j -= 1;
-            } else {
-                j += 1;
-            }
-        }
+    fn part2(&self, points: Self::Input) -> Result<String> {
+        Ok(p2::run(points).to_string())
+    }
+}
 
-        points.retain(|p| p.0 != p1.0 && p.1 != p1.1);
-        points.push(p1);
+fn from_lines(lines: Vec<String>) -> Result<Pointset> {
+    let mut points = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        points.push(parse_pair(line).map_err(|e| e.at_line(i + 1))?);
+    }
 
-        points
-    } else {
-        vec![]
+    if points.is_empty() {
+        return Err(Error::parse("the input is empty"));
     }
+
+    Ok(points)
 }
 
-// TODO: Replace this with board::Board<u8>
-pub struct Oceanfloor {
-    board: Vec<Vec<u8>>,
+fn parse_pair(line: &str) -> Result<Pointpair> {
+    arrow_pair(line, parse, parse)
 }
 
-impl Oceanfloor {
-    /*
-      This is a warning because Vec<Point> is aliased as Pointset.
-      Because we want Pointset to be returned from a function as well,
-      it can't be an alias to [Point] (as suggested by clippy). Perhaps a
-      refactoring to make Pointset an actual struct would make sense here
-    */
-    #[allow(clippy::ptr_arg)]
-    fn new(points: &Pointset) -> Self {
-        let mut board: Vec<Vec<u8>> = Vec::new();
-
-        let (max_x, max_y) = Self::get_max_coords(points);
-
-        for _ in 0..max_x {
-            board.push(vec![0; max_y as usize]);
-        }
+fn parse(point: &str) -> Result<Coord> {
+    pair(point, ",", parse_number, parse_number)
+}
 
-        Self { board }
+// The points from p1 to p2, both included. Vents only ever line up
+// horizontally, vertically or diagonally at 45 degrees; diagonal lines
+// are left out unless `diagonals` is set.
+fn interval(p1: Coord, p2: Coord, diagonals: bool) -> Vec<Coord> {
+    let step = ((p2.0 - p1.0).signum(), (p2.1 - p1.1).signum());
+    if step.0 != 0 && step.1 != 0 && !diagonals {
+        return vec![];
     }
 
-    /*
-      This is a warning because Vec<Point> is aliased as Pointset.
-      Because we want Pointset to be returned from a function as well,
-      it can't be an alias to [Point] (as suggested by clippy). Perhaps a
-      refactoring to make Pointset an actual struct would make sense here
-    */
-    #[allow(clippy::ptr_arg)]
-    fn get_max_coords(points: &Pointset) -> (usize, usize) {
-        let max_p1_x = points.iter().map(|(p1, _)| p1.0).max().unwrap();
-        let max_p2_x = points.iter().map(|(_, p2)| p2.0).max().unwrap();
-        let max_p1_y = points.iter().map(|(p1, _)| p1.1).max().unwrap();
-        let max_p2_y = points.iter().map(|(_, p2)| p2.1).max().unwrap();
-
-        let max_x = *vec![max_p1_x, max_p2_x].iter().max().unwrap() + 1;
-        let max_y = *vec![max_p1_y, max_p2_y].iter().max().unwrap() + 1;
-
-        (max_x, max_y)
-    }
+    let len = (p2.0 - p1.0).abs().max((p2.1 - p1.1).abs());
+    (0..=len)
+        .map(|i| (p1.0 + i * step.0, p1.1 + i * step.1))
+        .collect()
 }
 
-fn count_twos(oceanfloor: &Oceanfloor) -> i32 {
-    let mut twos = 0;
-    for row in oceanfloor.board.iter() {
-        for cell in row {
-            if *cell > 1 {
-                twos += 1;
-            }
+// How many points of the ocean floor have more than one line of vents
+fn count_overlaps(points: Pointset, diagonals: bool) -> usize {
+    let mut oceanfloor: Grid<u32> = Grid::new();
+    for (p1, p2) in points {
+        for point in interval(p1, p2, diagonals) {
+            *oceanfloor.get_or_default(point) += 1;
         }
     }
-    twos
-}
 
-fn apply_points(oceanfloor: &mut Oceanfloor, points: Vec<Point>) {
-    for point in points {
-        let (x, y) = point;
-
-        oceanfloor.board[x][y] += 1;
-    }
+    oceanfloor.values().filter(|&&vents| vents > 1).count()
 }
 
 mod p2 {
-    use super::{apply_points, count_twos, interval, Oceanfloor, Point};
+    use super::{count_overlaps, Pointset};
 
-    pub fn run(mut oceanfloor: Oceanfloor, points: Vec<(Point, Point)>) -> i32 {
-        for (p1, p2) in points {
-            let interval = interval(p1, p2, true);
-            apply_points(&mut oceanfloor, interval);
-        }
-
-        count_twos(&oceanfloor)
+    pub fn run(points: Pointset) -> usize {
+        count_overlaps(points, true)
     }
 }
 
 mod p1 {
-    use super::{apply_points, count_twos, interval, Oceanfloor, Point};
-
-    pub fn run(mut oceanfloor: Oceanfloor, points: Vec<(Point, Point)>) -> i32 {
-        for (p1, p2) in points {
-            let interval = interval(p1, p2, false);
-            apply_points(&mut oceanfloor, interval);
-        }
-
-        count_twos(&oceanfloor)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    #[allow(unused_imports)]
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        // TODO: Fix this
-        // assert_eq!(run(false, true), "5");
-    }
+    use super::{count_overlaps, Pointset};
 
-    #[test]
-    fn test_p2() {
-        // TODO: Fix this
-        // assert_eq!(run(true, true), "12");
+    pub fn run(points: Pointset) -> usize {
+        count_overlaps(points, false)
     }
 }
@@ -1,15 +1,27 @@
-use crate::utils::input::read_file;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::input::{first_line, parse_number, separated, Input};
 
-pub fn run(extra: bool, test: bool) -> String {
-    let input = read_file("6", test);
+pub struct Day6;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(School::from_input(input)),
-            true => p2::run(parse_line(&input[0])),
-        }
-    )
+impl Solution for Day6 {
+    type Input = Vec<i32>;
+
+    const DAY: u8 = 6;
+    const NAME: &'static str = "Lanternfish";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        parse_line(first_line(&lines)?).map_err(|e| e.at_line(1))
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(School::from_lifetimes(input)).to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input).to_string())
+    }
 }
 
 pub struct School {
@@ -34,9 +46,7 @@ impl School {
         self.fishes = new_fishes;
     }
 
-    fn from_input(input: Vec<String>) -> School {
-        let lifetimes = parse_line(&input[0]);
-
+    fn from_lifetimes(lifetimes: Vec<i32>) -> School {
         let mut school = School::new();
         for lifetime in lifetimes {
             school.fishes.push(Fish::new(lifetime));
@@ -69,8 +79,15 @@ impl Fish {
     }
 }
 
-fn parse_line(line: &str) -> Vec<i32> {
-    line.split(',').map(|s| s.parse::<i32>().unwrap()).collect()
+fn parse_line(line: &str) -> Result<Vec<i32>> {
+    separated(line, ",", |s| {
+        let lifetime = parse_number(s)?;
+        // A fish's timer never goes above that of a newborn
+        if !(0..=8).contains(&lifetime) {
+            return Err(Error::parse(format!("{} is not a valid timer", lifetime)));
+        }
+        Ok(lifetime)
+    })
 }
 
 mod p2 {
@@ -104,18 +121,3 @@ mod p1 {
         school.fishes.len() as i64
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "5934");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "26984457539");
-    }
-}
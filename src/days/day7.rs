@@ -1,101 +1,82 @@
-use crate::utils::input::read_file;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::input::{first_line, parse_number, separated, Input};
 
-pub fn run(extra: bool, test: bool) -> String {
-    let lines = read_file("7", test);
-    let input = parse_line(&lines[0]);
+pub struct Day7;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(input),
-            true => p2::run(input),
-        }
-    )
+impl Solution for Day7 {
+    type Input = Vec<i32>;
+
+    const DAY: u8 = 7;
+    const NAME: &'static str = "The Treachery of Whales";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        separated(first_line(&lines)?, ",", position).map_err(|e| e.at_line(1))
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input).to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input).to_string())
+    }
 }
 
-fn parse_line(line: &str) -> Vec<i32> {
-    line.split(',').map(|s| s.parse::<i32>().unwrap()).collect()
+// A crab's horizontal position, which is never left of 0
+fn position(s: &str) -> Result<i32> {
+    match parse_number(s)? {
+        pos if pos >= 0 => Ok(pos),
+        pos => Err(Error::parse(format!(
+            "a position can't be negative, found {}",
+            pos
+        ))),
+    }
 }
 
 mod p1 {
+    use crate::utils::board::Board;
+
     pub fn run(input: Vec<i32>) -> i32 {
-        let max = *input.iter().max().unwrap() + 1;
-        let mut fuel = vec![vec![0; max as usize]; input.len()];
+        let max = *input.iter().max().unwrap();
+        let mut fuel = vec![vec![0; max as usize + 1]; input.len()];
 
         for (i, crab) in input.iter().enumerate() {
-            for pos in 0..max {
+            for pos in 0..=max {
                 fuel[i][pos as usize] = (pos - crab).abs();
             }
         }
 
-        let mut min = i32::MAX;
-        // This isn't actually needless range looping since
-        // we want to loop all rows for each column at a time.
-        // This cannot be done with iterators unless
-        // we transpose the matrix but this isn't trivial in rust
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..fuel[0].len() {
-            let mut sum = 0;
-            // See comment above for why we allow this
-            #[allow(clippy::needless_range_loop)]
-            for j in 0..fuel.len() {
-                sum += fuel[j][i];
-            }
-            if sum < min {
-                min = sum;
-            }
-        }
-
-        min
+        // Each column holds what every crab spends to get to one position
+        Board::from(fuel)
+            .columns()
+            .map(|column| column.sum())
+            .min()
+            .unwrap()
     }
 }
 
 mod p2 {
+    use crate::utils::board::Board;
+
     pub fn run(input: Vec<i32>) -> i32 {
-        let max = *input.iter().max().unwrap() + 1;
-        let mut fuel = vec![vec![0; max as usize]; input.len()];
+        let max = *input.iter().max().unwrap();
+        let mut fuel = vec![vec![0; max as usize + 1]; input.len()];
 
         for (i, crab) in input.iter().enumerate() {
-            for pos in 0..max {
+            for pos in 0..=max {
                 let distance = (pos - crab).abs();
                 let sum = (distance.pow(2) + distance) / 2;
                 fuel[i][pos as usize] = sum;
             }
         }
 
-        // This isn't actually needless range looping since
-        // we want to loop all rows for each column at a time.
-        // This cannot be done with iterators unless
-        // we transpose the matrix but this isn't trivial in rust
-        #[allow(clippy::needless_range_loop)]
-        let mut min = i32::MAX;
-        // See comment above for why we allow this
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..fuel[0].len() {
-            let mut sum = 0;
-            for j in 0..fuel.len() {
-                sum += fuel[j][i];
-            }
-            if sum < min {
-                min = sum;
-            }
-        }
-
-        min
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "37");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "168");
+        // See part 1
+        Board::from(fuel)
+            .columns()
+            .map(|column| column.sum())
+            .min()
+            .unwrap()
     }
 }
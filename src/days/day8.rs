@@ -1,17 +1,29 @@
-use crate::utils::input::read_file;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::input::{pair, parse_at, Input};
 
 type InputLine = Vec<(Vec<String>, Vec<String>)>;
 
-pub fn run(extra: bool, test: bool) -> String {
-    let input = parse_lines(read_file("8", test));
+pub struct Day8;
 
-    format!(
-        "{}",
-        match extra {
-            false => p1::run(input),
-            true => p2::run(input),
-        }
-    )
+impl Solution for Day8 {
+    type Input = InputLine;
+
+    const DAY: u8 = 8;
+    const NAME: &'static str = "Seven Segment Search";
+
+    fn parse(&self, input: Input) -> Result<Self::Input> {
+        let lines = input.lines()?;
+        parse_lines(lines)
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<String> {
+        Ok(p1::run(input).to_string())
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<String> {
+        Ok(p2::run(input)?.to_string())
+    }
 }
 
 /**
@@ -26,7 +38,7 @@ pub fn run(extra: bool, test: bool) -> String {
  *
  * # Examples
  *
- *  ```
+ *  ```text
  *  assert_eq!(
  *  parse_lines(vec!["1 2 3 4 5 6 7 8 9 10 | 1 2 3 4", "10 9 8 7 6 5 4 3 2 1 | 4 3 2 1"]),
  *  vec![
@@ -38,18 +50,31 @@ pub fn run(extra: bool, test: bool) -> String {
  * )
  * ```
  */
-fn parse_lines(input: Vec<String>) -> InputLine {
+fn parse_lines(input: Vec<String>) -> Result<InputLine> {
     let mut res = Vec::new();
 
-    for line in input {
-        let split: Vec<&str> = line.split(" | ").collect();
-        let left_split: Vec<String> = split[0].split_whitespace().map(|s| s.to_string()).collect();
-        let right_split: Vec<String> = split[1].split_whitespace().map(|s| s.to_string()).collect();
-
-        res.push((left_split, right_split));
+    for (i, line) in input.iter().enumerate() {
+        let (signals, outputs) =
+            pair(line, " | ", patterns, patterns).map_err(|e| e.at_line(i + 1))?;
+        res.push((signals, outputs));
     }
 
-    res
+    Ok(res)
+}
+
+// The whitespace separated segment patterns of one side of a line
+fn patterns(side: &str) -> Result<Vec<String>> {
+    side.split_whitespace()
+        .map(|token| parse_at(side, token, pattern))
+        .collect()
+}
+
+fn pattern(s: &str) -> Result<String> {
+    if (1..=7).contains(&s.len()) && s.chars().all(|c| ('a'..='g').contains(&c)) {
+        Ok(s.to_string())
+    } else {
+        Err(Error::parse(format!("'{}' is not a segment pattern", s)))
+    }
 }
 
 mod p1 {
@@ -72,6 +97,7 @@ mod p1 {
 
 mod p2 {
     use super::InputLine;
+    use crate::error::{Error, Result};
 
     /*
      * This is uber confusing but it's pretty simple:
@@ -109,10 +135,17 @@ mod p2 {
      *    (this time 9 containing the entirety of 4, 6 containing the L and 0 being
      *    the remaining one), we have a mapping of all numbers.
      */
-    pub fn run(input: InputLine) -> u32 {
+    pub fn run(input: InputLine) -> Result<u32> {
         let mut sum = 0;
-        for line in input {
+        for (i, line) in input.into_iter().enumerate() {
             let digits = deep_sort(line.0);
+            // The patterns of 1, 4, 7 & 8 are the starting point of the deduction
+            if [1, 2, 3, 6].iter().any(|&len| digits[len].is_empty()) {
+                return Err(Error::unsolvable(format!(
+                    "line {} is missing the patterns of 1, 4, 7 or 8",
+                    i + 1
+                )));
+            }
             let mut numbers = [
                 &digits[1][0], // 1
                 "",            // 2
@@ -172,9 +205,11 @@ mod p2 {
                 }
             }
 
-            sum += res_digit.parse::<u32>().unwrap();
+            sum += res_digit.parse::<u32>().map_err(|_| {
+                Error::unsolvable(format!("cannot decode the output of line {}", i + 1))
+            })?;
         }
-        sum
+        Ok(sum)
     }
 
     fn remove(a: &str, b: &str) -> String {
@@ -217,18 +252,3 @@ mod p2 {
         ret
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::run;
-
-    #[test]
-    fn test_p1() {
-        assert_eq!(run(false, true), "26");
-    }
-
-    #[test]
-    fn test_p2() {
-        assert_eq!(run(true, true), "61229");
-    }
-}
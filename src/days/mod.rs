@@ -1,3 +1,5 @@
+use crate::solution::Puzzle;
+
 pub mod day1;
 pub mod day10;
 pub mod day11;
@@ -14,3 +16,41 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+
+// Every solved day, in order. Registering a new day only requires adding it here.
+static SOLUTIONS: [&dyn Puzzle; 16] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+];
+
+pub fn all() -> &'static [&'static dyn Puzzle] {
+    &SOLUTIONS
+}
+
+pub fn find(day: u8) -> Option<&'static dyn Puzzle> {
+    SOLUTIONS.iter().copied().find(|p| p.day() == day)
+}
+
+pub fn latest() -> &'static dyn Puzzle {
+    SOLUTIONS[SOLUTIONS.len() - 1]
+}
+
+// One test per fixture in input/test, generated by build.rs
+#[cfg(test)]
+mod fixtures {
+    include!(concat!(env!("OUT_DIR"), "/fixtures.rs"));
+}
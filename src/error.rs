@@ -0,0 +1,174 @@
+use std::fmt;
+use std::io;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// Where in the input something went wrong. Lines and columns are 1-based.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Location {
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts: Vec<String> = [
+            self.file.clone(),
+            self.line.map(|l| l.to_string()),
+            self.column.map(|c| c.to_string()),
+        ]
+        .iter()
+        .flatten()
+        .cloned()
+        .collect();
+
+        write!(f, "{}", parts.join(":"))
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    // The input could not be read at all
+    Io { path: String, source: io::Error },
+    // The input was read but doesn't look like what the puzzle expects
+    Parse { location: Location, message: String },
+    // A `--param` value the day can't work with. It's not the input's fault,
+    // so it has no location in it.
+    Param { name: String, message: String },
+    // The input parsed fine but has no answer (e.g. no line is left standing)
+    Unsolvable(String),
+    // The part itself isn't solved yet, so any answer would be made up
+    Unsolved(String),
+}
+
+impl Error {
+    pub fn parse(message: impl Into<String>) -> Self {
+        Error::Parse {
+            location: Location::default(),
+            message: message.into(),
+        }
+    }
+
+    pub fn param(name: &str, message: impl Into<String>) -> Self {
+        Error::Param {
+            name: name.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn unsolvable(message: impl Into<String>) -> Self {
+        Error::Unsolvable(message.into())
+    }
+
+    pub fn unsolved(message: impl Into<String>) -> Self {
+        Error::Unsolved(message.into())
+    }
+
+    // The following only fill in what isn't known yet, so the innermost
+    // (i.e. most precise) location always wins.
+
+    pub fn in_file(self, file: &str) -> Self {
+        self.locate(|loc| {
+            loc.file.get_or_insert_with(|| file.to_string());
+        })
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        self.locate(|loc| {
+            loc.line.get_or_insert(line);
+        })
+    }
+
+    pub fn at_column(self, column: usize) -> Self {
+        self.locate(|loc| {
+            loc.column.get_or_insert(column);
+        })
+    }
+
+    // Translates the line number (if known), e.g. from the position of a
+    // line among those a day was given to its position in the file
+    pub fn map_line(self, f: impl FnOnce(usize) -> usize) -> Self {
+        self.locate(|loc| {
+            loc.line = loc.line.map(f);
+        })
+    }
+
+    // For errors found in a part of a line: moves the column from being
+    // relative to that part to being relative to the whole line
+    pub fn shift_column(self, by: usize) -> Self {
+        self.locate(|loc| {
+            if let Some(column) = &mut loc.column {
+                *column += by;
+            }
+        })
+    }
+
+    fn locate(mut self, f: impl FnOnce(&mut Location)) -> Self {
+        if let Error::Parse { location, .. } = &mut self {
+            f(location);
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } if source.kind() == io::ErrorKind::NotFound => {
+                write!(
+                    f,
+                    "{} does not exist, put your puzzle input there first",
+                    path
+                )
+            }
+            Error::Io { path, source } => write!(f, "cannot read {}: {}", path, source),
+            Error::Parse { location, message } if *location == Location::default() => {
+                write!(f, "{}", message)
+            }
+            Error::Parse { location, message } => write!(f, "{}: {}", location, message),
+            Error::Param { name, message } => write!(f, "parameter {}: {}", name, message),
+            Error::Unsolvable(message) => write!(f, "no answer: {}", message),
+            Error::Unsolved(message) => write!(f, "not solved yet: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Error;
+
+    #[test]
+    fn test_display() {
+        let err = Error::parse("'x' is not a number")
+            .at_column(3)
+            .at_line(2)
+            .in_file("input/day1.in");
+        assert_eq!(err.to_string(), "input/day1.in:2:3: 'x' is not a number");
+
+        let err = Error::parse("empty input").in_file("input/day6.in");
+        assert_eq!(err.to_string(), "input/day6.in: empty input");
+
+        let err =
+            Error::param("tiles", "there must be at least one tile").in_file("input/day15.in");
+        assert_eq!(
+            err.to_string(),
+            "parameter tiles: there must be at least one tile"
+        );
+    }
+
+    #[test]
+    fn test_innermost_location_wins() {
+        let err = Error::parse("bad").at_line(4).at_line(1);
+        assert_eq!(err.to_string(), "4: bad");
+    }
+}
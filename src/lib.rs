@@ -0,0 +1,19 @@
+/*
+ Advent of Code 2021 solutions, plus everything they are built on:
+
+   - `days`: every day's `Solution` and the registry of all of them
+   - `solution`: the `Solution` trait and its object-safe `Puzzle` counterpart
+   - `utils`: the 2D `Board`, input reading and parsing helpers
+   - `parsing`: the BITS packet `Parser` of day 16
+   - `runner`, `bench` and `verify`: running, timing and checking days
+
+ The `aoc-2021` binary is a thin CLI over this.
+*/
+pub mod bench;
+pub mod days;
+pub mod error;
+pub mod parsing;
+pub mod runner;
+pub mod solution;
+pub mod utils;
+pub mod verify;
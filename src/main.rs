@@ -1,11 +1,12 @@
 extern crate clap;
+use aoc_2021::error::Error;
+use aoc_2021::solution::Puzzle;
+use aoc_2021::utils::answers::answers_file;
+use aoc_2021::utils::input::Source;
+use aoc_2021::utils::params::Params;
+use aoc_2021::{bench, days, runner, verify};
 use clap::{App, Arg};
-
-mod days;
-mod parsing;
-mod utils;
-
-use crate::days::*;
+use std::process;
 
 fn main() {
     let matches = App::new("Advent of code!")
@@ -19,38 +20,207 @@ fn main() {
             Arg::new("problem_number")
                 .short('p')
                 .long("problem")
-                .takes_value(true),
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(Arg::new("list").short('l').long("list").takes_value(false))
+        .arg(
+            Arg::new("all")
+                .short('a')
+                .long("all")
+                .takes_value(false)
+                .conflicts_with("input"),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .takes_value(true)
+                .possible_values(["text", "json"])
+                .default_value("text")
+                .help("Output format of runs. `json` prints one JSON object per line"),
         )
         .arg(
             Arg::new("test_input")
                 .short('t')
                 .long("test")
-                .takes_value(false),
+                .takes_value(false)
+                .global(true),
+        )
+        .arg(
+            Arg::new("jobs")
+                .short('j')
+                .long("jobs")
+                .takes_value(true)
+                .value_name("N")
+                .default_value("1")
+                .global(true)
+                .help("How many days to run at the same time, when running more than one"),
+        )
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .takes_value(true)
+                .value_name("PATH")
+                .global(true)
+                .conflicts_with("test_input")
+                .help(
+                    "Solve the given file (or stdin for `-`) instead of the day's input. \
+                     Inputs are otherwise looked up in $AOC_INPUT_DIR, if set",
+                ),
+        )
+        .arg(
+            Arg::new("param")
+                .long("param")
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .value_name("NAME=VALUE")
+                .global(true)
+                .help(
+                    "Changes a setting of the days that take one, e.g. `tiles=50` \
+                     for how much day 15 enlarges its map. It's an error if none of \
+                     the days being solved takes it",
+                ),
+        )
+        .subcommand(
+            App::new("bench")
+                .about("Times reading, parsing and solving of one or every day")
+                .arg(
+                    Arg::new("iterations")
+                        .short('n')
+                        .long("iterations")
+                        .takes_value(true)
+                        .default_value("10"),
+                ),
+        )
+        .subcommand(
+            App::new("verify")
+                .about("Checks the answers for the real inputs against the recorded ones")
+                .arg(
+                    Arg::new("record")
+                        .long("record")
+                        .takes_value(false)
+                        .help("Record the current answers instead of checking them"),
+                ),
         )
         .get_matches();
 
+    if matches.is_present("list") {
+        for puzzle in days::all() {
+            println!("{:>2}: {}", puzzle.day(), puzzle.name());
+        }
+        return;
+    }
+
     let extra = matches.is_present("extra");
     let test = matches.is_present("test_input");
-    let problem = matches.value_of("problem_number").unwrap_or("16");
-    let answer: String = match problem {
-        "1" => day1::run(extra, test),
-        "2" => day2::run(extra, test),
-        "3" => day3::run(extra, test),
-        "4" => day4::run(extra, test),
-        "5" => day5::run(extra, test),
-        "6" => day6::run(extra, test),
-        "7" => day7::run(extra, test),
-        "8" => day8::run(extra, test),
-        "9" => day9::run(extra, test),
-        "10" => day10::run(extra, test),
-        "11" => day11::run(extra, test),
-        "12" => day12::run(extra, test),
-        "13" => day13::run(extra, test),
-        "14" => day14::run(extra, test),
-        "15" => day15::run(extra, test),
-        "16" => day16::run(extra, test),
-        &_ => format!("Only know how to solve #{:?} for now :(", (1..=16)),
+    let json = matches.value_of("format") == Some("json");
+    let problem = matches.value_of("problem_number");
+    // A specific input only makes sense for a single day
+    let selected = match (problem, matches.value_of("input")) {
+        (Some(problem), _) => vec![find_puzzle(problem)],
+        (None, Some(_)) => vec![days::latest()],
+        (None, None) => days::all().to_vec(),
     };
+    let jobs = positive(matches.value_of("jobs").unwrap(), "number of jobs");
+    let source = match matches.value_of("input") {
+        Some(arg) => Source::from_arg(arg).unwrap_or_else(|e| fail(e)),
+        None => Source::Default { test },
+    };
+    let params =
+        Params::parse(matches.values_of("param").into_iter().flatten()).unwrap_or_else(|e| fail(e));
+
+    if let Some(bench_matches) = matches.subcommand_matches("bench") {
+        let iterations = positive(
+            bench_matches.value_of("iterations").unwrap(),
+            "number of iterations",
+        );
+
+        params.check(&selected).unwrap_or_else(|e| fail(e));
+        bench::run(&selected, &source, &params, iterations);
+        return;
+    }
+
+    if let Some(verify_matches) = matches.subcommand_matches("verify") {
+        if !matches!(source, Source::Default { test: false }) || !params.is_empty() {
+            eprintln!("Answers are only recorded for the real inputs, as the puzzles ask");
+            process::exit(1);
+        }
+
+        let verified = if verify_matches.is_present("record") {
+            verify::record(&selected, &answers_file(), jobs).map(|_| true)
+        } else {
+            verify::verify(&selected, &answers_file(), jobs)
+        };
+
+        match verified {
+            Ok(true) => (),
+            Ok(false) => process::exit(1),
+            Err(e) => fail(e),
+        }
+        return;
+    }
+
+    if matches.is_present("all") {
+        params.check(days::all()).unwrap_or_else(|e| fail(e));
+        let outcomes = runner::run_all(days::all(), &source, &params, jobs);
+        if json {
+            outcomes.iter().for_each(|o| println!("{}", o.to_json()));
+        } else {
+            runner::print_table(&outcomes);
+        }
+
+        if outcomes.iter().any(|o| o.is_failure()) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let puzzle = problem.map_or_else(days::latest, find_puzzle);
+    params.check(&[puzzle]).unwrap_or_else(|e| fail(e));
+    if json {
+        let outcome = runner::run_one(puzzle, if extra { 2 } else { 1 }, &source, &params);
+        println!("{}", outcome.to_json());
+
+        if outcome.is_failure() {
+            process::exit(1);
+        }
+    } else {
+        match runner::guarded(|| puzzle.run(extra, &source, &params)) {
+            Ok(Ok(answer)) => println!("{}", answer),
+            Ok(Err(e)) => fail(e),
+            Err(msg) => {
+                eprintln!("error: day {} panicked: {}", puzzle.day(), msg);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn positive(value: &str, what: &str) -> usize {
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => n,
+        _ => {
+            eprintln!("The {} must be a positive number", what);
+            process::exit(1);
+        }
+    }
+}
+
+fn fail(e: Error) -> ! {
+    eprintln!("error: {}", e);
+    process::exit(1);
+}
 
-    println!("{}", answer);
+fn find_puzzle(problem: &str) -> &'static dyn Puzzle {
+    match problem.parse::<u8>().ok().and_then(days::find) {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!(
+                "Only know how to solve {:?} for now :(",
+                days::all().iter().map(|p| p.day()).collect::<Vec<u8>>()
+            );
+            process::exit(1);
+        }
+    }
 }
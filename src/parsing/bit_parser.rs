@@ -1,72 +1,88 @@
+use crate::error::{Error, Result};
 use crate::parsing::bit_range::BitRange;
+use std::convert::TryFrom;
 
 pub struct Parser(BitRange);
 
 impl Parser {
-    pub fn execute(&mut self) -> isize {
-        let (_, type_id) = self.header();
+    pub fn execute(&mut self) -> Result<isize> {
+        let (_, type_id) = self.header()?;
 
         if type_id == 4 {
             // Literal packet
             self.decode_literal()
         } else {
-            self.decode_operator(Operator::from(type_id))
+            let op = Operator::try_from(type_id)?;
+            self.decode_operator(op)
         }
     }
 
-    pub fn versions(&mut self) -> isize {
-        let (version, type_id) = self.header();
+    pub fn versions(&mut self) -> Result<isize> {
+        let (version, type_id) = self.header()?;
 
         let mut version_sum = version;
 
         if type_id == 4 {
-            self.decode_literal();
+            self.decode_literal()?;
         } else {
-            version_sum += self.decode_operator_version()
+            version_sum += self.decode_operator_version()?
         }
 
-        version_sum
+        Ok(version_sum)
     }
 
-    fn decode_operator_version(&mut self) -> isize {
-        let length_type_id = self.length_type_id();
+    fn decode_operator_version(&mut self) -> Result<isize> {
+        let length_type_id = self.length_type_id()?;
         let mut version_sum = 0;
 
         if length_type_id == 1 {
-            let sub_packets = self.0.slice(11).unwrap().as_isize().unwrap();
+            let sub_packets = self.0.read(11)?.as_isize()?;
             for _ in 0..sub_packets {
-                version_sum += self.versions();
+                version_sum += self.versions()?;
             }
         } else {
-            let num_bits = self.0.slice(15).unwrap().as_isize().unwrap() as usize;
+            let num_bits = self.0.read(15)?.as_isize()? as usize;
             let target = self.0.pos() + num_bits;
             while self.0.pos() < target {
-                version_sum += self.versions();
+                version_sum += self.versions()?;
             }
         }
 
-        version_sum
+        Ok(version_sum)
     }
 
-    fn decode_operator(&mut self, op: Operator) -> isize {
-        let length_type_id = self.length_type_id();
+    fn decode_operator(&mut self, op: Operator) -> Result<isize> {
+        let length_type_id = self.length_type_id()?;
 
         let mut sub_packet_results: Vec<isize> = Vec::new();
 
         if length_type_id == 1 {
-            let sub_packets = self.0.slice(11).unwrap().as_isize().unwrap();
+            let sub_packets = self.0.read(11)?.as_isize()?;
             for _ in 0..sub_packets {
-                sub_packet_results.push(self.execute())
+                sub_packet_results.push(self.execute()?)
             }
         } else {
-            let num_bits = self.0.slice(15).unwrap().as_isize().unwrap() as usize;
+            let num_bits = self.0.read(15)?.as_isize()? as usize;
             let target = self.0.pos() + num_bits;
             while self.0.pos() < target {
-                sub_packet_results.push(self.execute());
+                sub_packet_results.push(self.execute()?);
             }
         }
 
-        match op {
+        let expected_operands = match op {
+            Operator::GreaterThan | Operator::LessThan | Operator::Equal => 2,
+            _ => 1,
+        };
+        if sub_packet_results.len() < expected_operands {
+            return Err(Error::parse(format!(
+                "operator packet needs at least {} sub-packets, found {}",
+                expected_operands,
+                sub_packet_results.len()
+            ))
+            .at_column(self.0.pos() / 4 + 1));
+        }
+
+        Ok(match op {
             Operator::Sum => sub_packet_results.iter().sum(),
             Operator::Product => sub_packet_results.iter().product(),
             Operator::Min => *sub_packet_results.iter().min().unwrap(),
@@ -92,21 +108,21 @@ impl Parser {
                     0
                 }
             }
-        }
+        })
     }
 
-    fn decode_literal(&mut self) -> isize {
+    fn decode_literal(&mut self) -> Result<isize> {
         // Literal
         let mut literal: Vec<char> = Vec::new();
 
         loop {
             let mut is_last_batch = false;
-            let mut batch: BitRange = self.0.slice(5).unwrap();
-            if batch.next() == '0' {
+            let mut batch: BitRange = self.0.read(5)?;
+            if batch.next()? == '0' {
                 is_last_batch = true
             }
 
-            let mut next_4 = batch.take(4);
+            let mut next_4 = batch.take(4)?;
             literal.append(&mut next_4);
 
             if is_last_batch {
@@ -114,30 +130,32 @@ impl Parser {
             }
         }
 
-        BitRange::from(&literal[..]).as_isize().unwrap()
+        BitRange::from(&literal[..]).as_isize()
     }
 
     // Returns a tuple containing the version and type_id
-    fn header(&mut self) -> (isize, isize) {
-        (self.version(), self.type_id())
+    fn header(&mut self) -> Result<(isize, isize)> {
+        Ok((self.version()?, self.type_id()?))
     }
 
-    fn length_type_id(&mut self) -> isize {
-        self.0.slice(1).unwrap().as_isize().unwrap()
+    fn length_type_id(&mut self) -> Result<isize> {
+        self.0.read(1)?.as_isize()
     }
 
-    fn version(&mut self) -> isize {
-        self.0.slice(3).unwrap().as_isize().unwrap()
+    fn version(&mut self) -> Result<isize> {
+        self.0.read(3)?.as_isize()
     }
 
-    fn type_id(&mut self) -> isize {
-        self.0.slice(3).unwrap().as_isize().unwrap()
+    fn type_id(&mut self) -> Result<isize> {
+        self.0.read(3)?.as_isize()
     }
 }
 
-impl From<&str> for Parser {
-    fn from(s: &str) -> Self {
-        Parser(BitRange::from(s))
+impl TryFrom<&str> for Parser {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        Ok(Parser(BitRange::try_from(s)?))
     }
 }
 
@@ -151,9 +169,11 @@ enum Operator {
     Equal,
 }
 
-impl From<isize> for Operator {
-    fn from(i: isize) -> Self {
-        match i {
+impl TryFrom<isize> for Operator {
+    type Error = Error;
+
+    fn try_from(i: isize) -> Result<Self> {
+        Ok(match i {
             0 => Operator::Sum,
             1 => Operator::Product,
             2 => Operator::Min,
@@ -161,7 +181,7 @@ impl From<isize> for Operator {
             5 => Operator::GreaterThan,
             6 => Operator::LessThan,
             7 => Operator::Equal,
-            _ => panic!("Unrecognized operator: {}", i),
-        }
+            _ => return Err(Error::parse(format!("Unrecognized operator: {}", i))),
+        })
     }
 }
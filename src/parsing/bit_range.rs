@@ -1,4 +1,5 @@
-use std::num::ParseIntError;
+use crate::error::{Error, Result};
+use std::convert::TryFrom;
 
 #[derive(Debug)]
 pub struct BitRange {
@@ -13,21 +14,34 @@ impl BitRange {
         self.range(old_pos, old_pos + length)
     }
 
-    pub fn take(&mut self, length: usize) -> Vec<char> {
-        self.slice(length).unwrap().bitstring
+    // Like `slice`, but running out of bits is an error rather than `None`
+    pub fn read(&mut self, length: usize) -> Result<BitRange> {
+        let pos = self.pos;
+        self.slice(length).ok_or_else(|| {
+            // Report the hex digit we were in the middle of
+            Error::parse(format!("packet ended while reading {} more bits", length))
+                .at_column(pos / 4 + 1)
+        })
+    }
+
+    pub fn take(&mut self, length: usize) -> Result<Vec<char>> {
+        Ok(self.read(length)?.bitstring)
     }
 
     // Non inclusive end
-    pub fn next(&mut self) -> char {
-        self.take(1)[0]
+    pub fn next(&mut self) -> Result<char> {
+        Ok(self.take(1)?[0])
     }
 
     pub fn as_str(&self) -> String {
         self.bitstring.iter().collect()
     }
 
-    pub fn as_isize(&self) -> Result<isize, ParseIntError> {
+    // The range only ever contains 0s and 1s, so this can only fail
+    // if it's too long to fit in an isize
+    pub fn as_isize(&self) -> Result<isize> {
         isize::from_str_radix(&self.as_str(), 2)
+            .map_err(|_| Error::parse(format!("{} does not fit in a number", self.as_str())))
     }
 
     pub fn pos(&self) -> usize {
@@ -38,8 +52,8 @@ impl BitRange {
         self.bitstring.get(start..end).map(BitRange::from)
     }
 
-    fn lookup(hex: char) -> Vec<char> {
-        match hex {
+    fn lookup(hex: char) -> Option<&'static str> {
+        Some(match hex {
             '0' => "0000",
             '1' => "0001",
             '2' => "0010",
@@ -56,22 +70,25 @@ impl BitRange {
             'D' => "1101",
             'E' => "1110",
             'F' => "1111",
-            x => panic!("Unrecognized hex digit: {}", x),
-        }
-        .chars()
-        .collect()
+            _ => return None,
+        })
     }
 }
 
-impl From<&str> for BitRange {
-    fn from(hex: &str) -> Self {
+impl TryFrom<&str> for BitRange {
+    type Error = Error;
+
+    fn try_from(hex: &str) -> Result<Self> {
         let mut bitstring: Vec<char> = Vec::new();
 
-        for hexdigit in hex.chars() {
-            bitstring.append(&mut Self::lookup(hexdigit));
+        for (i, hexdigit) in hex.chars().enumerate() {
+            let bits = Self::lookup(hexdigit).ok_or_else(|| {
+                Error::parse(format!("'{}' is not a hex digit", hexdigit)).at_column(i + 1)
+            })?;
+            bitstring.extend(bits.chars());
         }
 
-        Self { bitstring, pos: 0 }
+        Ok(Self { bitstring, pos: 0 })
     }
 }
 
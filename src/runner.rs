@@ -0,0 +1,358 @@
+use crate::error::Error;
+use crate::solution::{Puzzle, Timings};
+use crate::utils::input::Source;
+use crate::utils::json::Json;
+use crate::utils::params::Params;
+use crate::utils::table::{self, Align};
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Once};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub enum Status {
+    Ok,
+    // The puzzle reported an error (e.g. the input could not be parsed)
+    Failed(String),
+    // The puzzle panicked. This is always a bug in the puzzle itself.
+    Panicked(String),
+    // The part isn't solved yet. That's known, so it's not a failure.
+    Unsolved(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Failed(msg) => write!(f, "error: {}", msg),
+            Status::Panicked(msg) => write!(f, "panicked: {}", msg),
+            Status::Unsolved(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+// The result of running a single part of a single day
+pub struct Outcome {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<String>,
+    pub timings: Timings,
+    pub status: Status,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, Status::Ok)
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, Status::Failed(_) | Status::Panicked(_))
+    }
+
+    pub fn to_json(&self) -> Json {
+        let ms = |d: Duration| Json::Float(d.as_micros() as f64 / 1000.0);
+        let error = match &self.status {
+            Status::Ok => None,
+            Status::Failed(msg) | Status::Panicked(msg) | Status::Unsolved(msg) => Some(&msg[..]),
+        };
+
+        Json::Object(vec![
+            ("day", Json::Int(self.day as i64)),
+            ("part", Json::Int(self.part as i64)),
+            ("input", Json::from(&self.input[..])),
+            ("ok", Json::Bool(self.is_ok())),
+            ("answer", Json::from(self.answer.as_deref())),
+            (
+                "timings_ms",
+                Json::Object(vec![
+                    ("read", ms(self.timings.read)),
+                    ("parse", ms(self.timings.parse)),
+                    ("solve", ms(self.timings.solve)),
+                    ("total", ms(self.timings.total())),
+                ]),
+            ),
+            ("error", Json::from(error)),
+        ])
+    }
+}
+
+// Runs both parts of every given puzzle. A failing (or panicking) part is
+// recorded in its outcome instead of bringing the whole run down.
+//
+// Days are independent of each other, so up to `jobs` of them are run at
+// the same time. The outcomes are still in the order the puzzles were given.
+pub fn run_all(
+    puzzles: &[&dyn Puzzle],
+    source: &Source,
+    params: &Params,
+    jobs: usize,
+) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, puzzles.len().max(1)) {
+            let tx = tx.clone();
+            let next = &next;
+
+            // Every worker keeps taking the next day nobody has started yet
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let puzzle = match puzzles.get(i) {
+                    Some(puzzle) => *puzzle,
+                    None => break,
+                };
+
+                let outcomes: Vec<Outcome> = (1..=2)
+                    .map(|part| run_part(puzzle, part, source, params))
+                    .collect();
+                tx.send((i, outcomes)).unwrap();
+            });
+        }
+    });
+    drop(tx);
+
+    let mut days: Vec<(usize, Vec<Outcome>)> = rx.into_iter().collect();
+    days.sort_by_key(|(i, _)| *i);
+    days.into_iter()
+        .flat_map(|(_, outcomes)| outcomes)
+        .collect()
+}
+
+// Runs a single part of a single puzzle, recording a panic in the outcome
+pub fn run_one(puzzle: &dyn Puzzle, part: u8, source: &Source, params: &Params) -> Outcome {
+    run_part(puzzle, part, source, params)
+}
+
+fn run_part(puzzle: &dyn Puzzle, part: u8, source: &Source, params: &Params) -> Outcome {
+    let (answer, timings, status) = match guarded(|| puzzle.run_timed(part == 2, source, params)) {
+        Ok(Ok((answer, timings))) => (Some(answer), timings, Status::Ok),
+        Ok(Err(e @ Error::Unsolved(_))) => {
+            (None, Timings::default(), Status::Unsolved(e.to_string()))
+        }
+        Ok(Err(e)) => (None, Timings::default(), Status::Failed(e.to_string())),
+        Err(msg) => (None, Timings::default(), Status::Panicked(msg)),
+    };
+
+    Outcome {
+        day: puzzle.day(),
+        part,
+        input: source.describe(puzzle.day()),
+        answer,
+        timings,
+        status,
+    }
+}
+
+thread_local! {
+    // Whether this thread is inside `guarded`, whose panics are caught
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+// Runs `f`, turning a panic into an error holding the panic message. The
+// panic isn't printed, since it's reported through the error instead.
+pub fn guarded<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    install_quiet_hook();
+
+    let outer = GUARDED.with(|guarded| guarded.replace(true));
+    let res = panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message);
+    GUARDED.with(|guarded| guarded.set(outer));
+
+    res
+}
+
+/*
+ The default panic hook prints every panic (and its location), including
+ the ones `guarded` catches, in the middle of our output. This wraps it
+ once for the whole process, leaving it out only for the panics of
+ threads inside `guarded`. Panics anywhere else are printed as always.
+*/
+fn install_quiet_hook() {
+    QUIET_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !GUARDED.with(|guarded| guarded.get()) {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    format!("{:.3}ms", d.as_secs_f64() * 1000.0)
+}
+
+pub fn print_table(outcomes: &[Outcome]) {
+    let rows: Vec<Vec<String>> = outcomes
+        .iter()
+        .map(|o| {
+            vec![
+                o.day.to_string(),
+                o.part.to_string(),
+                o.answer.clone().unwrap_or_else(|| "-".to_string()),
+                format_duration(o.timings.total()),
+                o.status.to_string(),
+            ]
+        })
+        .collect();
+
+    table::print(
+        &[
+            ("Day", Align::Right),
+            ("Part", Align::Right),
+            ("Answer", Align::Left),
+            ("Time", Align::Right),
+            ("Status", Align::Left),
+        ],
+        &rows,
+    );
+
+    let passed = outcomes.iter().filter(|o| o.is_ok()).count();
+    let failed = outcomes.iter().filter(|o| o.is_failure()).count();
+    let unsolved = outcomes.len() - passed - failed;
+    println!();
+    if unsolved > 0 {
+        println!(
+            "{} passed, {} failed, {} not solved yet",
+            passed, failed, unsolved
+        );
+    } else {
+        println!("{} passed, {} failed", passed, failed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_all, Status};
+    use crate::days;
+    use crate::error::{Error, Result};
+    use crate::solution::{Puzzle, Solution};
+    use crate::utils::input::{Input, Source};
+    use crate::utils::params::Params;
+
+    struct Panicky;
+
+    impl Solution for Panicky {
+        type Input = ();
+
+        const DAY: u8 = 1;
+        const NAME: &'static str = "Panicky";
+
+        fn parse(&self, _: Input) -> Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part1(&self, _: Self::Input) -> Result<String> {
+            Ok("fine".to_string())
+        }
+
+        fn part2(&self, _: Self::Input) -> Result<String> {
+            panic!("part 2 is broken")
+        }
+    }
+
+    struct Failing;
+
+    impl Solution for Failing {
+        type Input = ();
+
+        const DAY: u8 = 1;
+        const NAME: &'static str = "Failing";
+
+        fn parse(&self, input: Input) -> Result<Self::Input> {
+            input.lines()?;
+            Err(Error::parse("bad input").at_line(3))
+        }
+
+        fn part1(&self, _: Self::Input) -> Result<String> {
+            Ok("unreachable".to_string())
+        }
+
+        fn part2(&self, _: Self::Input) -> Result<String> {
+            Ok("unreachable".to_string())
+        }
+    }
+
+    #[test]
+    fn test_panics_are_reported() {
+        let puzzles: [&dyn Puzzle; 1] = [&Panicky];
+        let outcomes = run_all(
+            &puzzles,
+            &Source::Default { test: true },
+            &Params::default(),
+            1,
+        );
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].answer.as_deref(), Some("fine"));
+        assert!(outcomes[0].is_ok());
+        assert!(outcomes[1].answer.is_none());
+        assert!(matches!(&outcomes[1].status, Status::Panicked(msg) if msg == "part 2 is broken"));
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        let puzzles: [&dyn Puzzle; 1] = [&Failing];
+        let outcomes = run_all(
+            &puzzles,
+            &Source::Default { test: true },
+            &Params::default(),
+            1,
+        );
+
+        assert!(outcomes.iter().all(|o| o.answer.is_none()));
+        assert!(matches!(
+            &outcomes[0].status,
+            Status::Failed(msg) if msg == "input/test/day1.in:5: bad input"
+        ));
+    }
+
+    #[test]
+    fn test_unsolved_parts_are_not_failures() {
+        let outcomes = run_all(
+            &[days::find(4).unwrap()],
+            &Source::Default { test: true },
+            &Params::default(),
+            1,
+        );
+
+        assert!(outcomes[0].is_ok());
+        assert!(matches!(&outcomes[1].status, Status::Unsolved(_)));
+        assert!(!outcomes[1].is_ok() && !outcomes[1].is_failure());
+    }
+
+    #[test]
+    fn test_jobs_keep_the_order() {
+        let source = Source::Default { test: true };
+        let answers = |jobs| -> Vec<(u8, u8, Option<String>)> {
+            run_all(days::all(), &source, &Params::default(), jobs)
+                .into_iter()
+                .map(|o| (o.day, o.part, o.answer))
+                .collect()
+        };
+
+        let sequential = answers(1);
+        assert_eq!(sequential.len(), 2 * days::all().len());
+        assert!(sequential
+            .windows(2)
+            .all(|w| (w[0].0, w[0].1) < (w[1].0, w[1].1)));
+        assert_eq!(answers(4), sequential);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::error::Result;
+use crate::utils::input::{Input, Source};
+use crate::utils::params::Params;
+use std::time::{Duration, Instant};
+
+// A single day's puzzle: how its input is parsed and how both parts are solved.
+// Each part receives its own freshly parsed input, since most solutions
+// consume (or mutate) what they are given.
+pub trait Solution {
+    type Input;
+
+    const DAY: u8;
+    const NAME: &'static str;
+
+    // Lines starting with this are left out of the input. Days without
+    // one see every line, '#' and all.
+    const COMMENT: Option<&'static str> = None;
+
+    // Whether the day goes through its input as it solves (see
+    // `Input::stream`). The input of every other day is read in full
+    // before it is parsed.
+    const STREAMS: bool = false;
+
+    // The names of the `--param`s the day takes (see `utils::params`)
+    const PARAMS: &'static [&'static str] = &[];
+
+    fn parse(&self, input: Input) -> Result<Self::Input>;
+
+    fn part1(&self, input: Self::Input) -> Result<String>;
+
+    fn part2(&self, input: Self::Input) -> Result<String>;
+}
+
+// How long each phase of solving a single part took
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Timings {
+    // Opening and reading the input. Days that stream their input read it
+    // as they solve, so for them this is only the opening.
+    pub read: Duration,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.read + self.parse + self.solve
+    }
+}
+
+// Object-safe counterpart of `Solution`. Every `Solution` gets this for free,
+// which is what lets days with different input types share one registry.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn name(&self) -> &'static str;
+
+    fn streams(&self) -> bool;
+
+    fn params(&self) -> &'static [&'static str];
+
+    // Solves part 1 (or part 2 if `extra` is set), timing parsing and solving
+    // separately. The `read` timing is left empty since the input is given.
+    fn solve_timed(&self, input: Input, extra: bool) -> Result<(String, Timings)>;
+
+    fn solve(&self, input: Input, extra: bool) -> Result<String> {
+        self.solve_timed(input, extra).map(|(answer, _)| answer)
+    }
+
+    // Reads the input and solves it with the given `params`, timing every
+    // phase. Days that stream their input only have it opened here, the
+    // rest is read as they solve.
+    fn run_timed(
+        &self,
+        extra: bool,
+        source: &Source,
+        params: &Params,
+    ) -> Result<(String, Timings)> {
+        let start = Instant::now();
+        let mut input = source.open(self.day())?.with_params(params.clone());
+        if !self.streams() {
+            input = input.buffered()?;
+        }
+        let read = start.elapsed();
+
+        let (answer, timings) = self
+            .solve_timed(input, extra)
+            .map_err(|e| e.in_file(&source.describe(self.day())))?;
+        Ok((answer, Timings { read, ..timings }))
+    }
+
+    fn run(&self, extra: bool, source: &Source, params: &Params) -> Result<String> {
+        self.run_timed(extra, source, params)
+            .map(|(answer, _)| answer)
+    }
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution + Sync,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn streams(&self) -> bool {
+        S::STREAMS
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        S::PARAMS
+    }
+
+    fn solve_timed(&self, input: Input, extra: bool) -> Result<(String, Timings)> {
+        let numbers = input.numbers();
+        let input = input.with_comment(S::COMMENT);
+
+        let solved = || -> Result<(String, Timings)> {
+            let start = Instant::now();
+            let input = self.parse(input)?;
+            let parse = start.elapsed();
+
+            let start = Instant::now();
+            let answer = match extra {
+                false => self.part1(input)?,
+                true => self.part2(input)?,
+            };
+            let solve = start.elapsed();
+
+            Ok((
+                answer,
+                Timings {
+                    parse,
+                    solve,
+                    ..Timings::default()
+                },
+            ))
+        };
+
+        solved().map_err(|e| e.map_line(|n| numbers.get(n)))
+    }
+}
@@ -0,0 +1,180 @@
+use crate::error::{Error, Result};
+use crate::utils::input::input_dir;
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+
+// Lives next to the inputs the answers belong to
+pub fn answers_file() -> String {
+    input_dir().join("answers.toml").display().to_string()
+}
+
+/*
+ Expected answers for the real inputs, keyed by day and part.
+ They are stored in a small subset of TOML, which is all we need
+ and saves us from pulling in a TOML crate:
+
+   [day1]
+   part1 = "1374"
+   part2 = "1418"
+*/
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u8, u8), String>);
+
+impl Answers {
+    // Loads the answers from `path`. A missing file simply means nothing
+    // has been recorded yet.
+    pub fn load(path: &str) -> Result<Self> {
+        if !Path::new(path).exists() {
+            return Ok(Self::default());
+        }
+
+        let content = read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_string(),
+            source,
+        })?;
+        Self::parse(&content).map_err(|e| e.in_file(path))
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        write(path, self.to_toml()).map_err(|source| Error::Io {
+            path: path.to_string(),
+            source,
+        })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(|s| &s[..])
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: String) {
+        self.0.insert((day, part), answer);
+    }
+
+    pub fn remove(&mut self, day: u8, part: u8) {
+        self.0.remove(&(day, part));
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut answers = Self::default();
+        let mut day: Option<u8> = None;
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || Error::parse(format!("malformed line '{}'", line)).at_line(i + 1);
+
+            if let Some(table) = line.strip_prefix("[day").and_then(|l| l.strip_suffix(']')) {
+                day = Some(table.parse::<u8>().map_err(|_| malformed())?);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(malformed)?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|p| p.parse::<u8>().ok())
+                .ok_or_else(malformed)?;
+            let value = unquote(value.trim()).ok_or_else(malformed)?;
+            let day = day.ok_or_else(|| {
+                Error::parse("answer is outside of a [dayN] table").at_line(i + 1)
+            })?;
+
+            answers.set(day, part, value);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::from(
+            "# Expected answers for the real inputs. Update with `verify --record`.\n",
+        );
+        let mut current_day = None;
+
+        for ((day, part), answer) in self.0.iter() {
+            if current_day != Some(*day) {
+                toml.push_str(&format!("\n[day{}]\n", day));
+                current_day = Some(*day);
+            }
+            toml.push_str(&format!("part{} = {}\n", part, quote(answer)));
+        }
+
+        toml
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn unquote(s: &str) -> Option<String> {
+    unescape(s.strip_prefix('"')?.strip_suffix('"')?)
+}
+
+// Undoes the escapes of `quote`, e.g. `\n` back into a line break. None if
+// there is an escape `quote` doesn't make.
+pub fn unescape(s: &str) -> Option<String> {
+    let mut unquoted = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unquoted.push(match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                _ => return None,
+            });
+        } else {
+            unquoted.push(c);
+        }
+    }
+
+    Some(unquoted)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answers;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut answers = Answers::default();
+        answers.set(1, 1, "7".to_string());
+        answers.set(1, 2, "5".to_string());
+        answers.set(13, 2, "#..#\n\"#\"".to_string());
+
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            Answers::parse("# comment\n\n[day2]\npart1 = \"150\"\n part2=\"900\" \n").unwrap();
+
+        assert_eq!(answers.get(2, 1), Some("150"));
+        assert_eq!(answers.get(2, 2), Some("900"));
+        assert_eq!(answers.get(1, 1), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Answers::parse("part1 = \"1\"").is_err());
+        assert!(Answers::parse("[day1]\npart1 = 1").is_err());
+        assert!(Answers::parse("[dayX]").is_err());
+    }
+}
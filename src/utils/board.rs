@@ -1,84 +1,339 @@
+use crate::error::{Error, Result};
+use std::convert::TryInto;
+use std::ops::Sub;
+
 pub type Point = (usize, usize);
 
-#[derive(Debug)]
-pub struct Board<T>(Vec<Vec<T>>);
+// A part of a board, both corners included. Its coordinates are a board's
+// unless given otherwise, e.g. `Rect<i64>` for the bounds of a `Grid`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect<N = usize> {
+    pub min: (N, N),
+    pub max: (N, N),
+}
+
+impl<N> Rect<N>
+where
+    N: Copy + Ord,
+{
+    // The rectangle covering nothing but `p`
+    pub fn around(p: (N, N)) -> Self {
+        Rect { min: p, max: p }
+    }
+
+    // Grows the rectangle to cover `p`
+    pub fn extend(&mut self, p: (N, N)) {
+        self.min = (self.min.0.min(p.0), self.min.1.min(p.1));
+        self.max = (self.max.0.max(p.0), self.max.1.max(p.1));
+    }
+
+    pub fn contains(&self, p: (N, N)) -> bool {
+        (self.min.0..=self.max.0).contains(&p.0) && (self.min.1..=self.max.1).contains(&p.1)
+    }
+}
+
+impl<N> Rect<N>
+where
+    N: Copy + Sub<Output = N> + TryInto<usize>,
+{
+    // In rows and columns, as for `Board::dim`
+    pub fn dim(&self) -> (usize, usize) {
+        let len = |min: N, max: N| match (max - min).try_into() {
+            Ok(len) => len + 1,
+            Err(_) => panic!("a rectangle's max corner can't come before its min"),
+        };
+
+        (len(self.min.0, self.max.0), len(self.min.1, self.max.1))
+    }
+}
+
+/*
+ A rectangular grid of cells, addressed by (row, column). The cells are
+ stored row after row in one Vec, so walking the board (or a row of it)
+ walks memory in order.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Board<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
 
 impl<T> Board<T> {
-    pub fn from(vec: Vec<Vec<T>>) -> Self {
-        Board(vec)
+    // Every row must be as long as the first one
+    pub fn from(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "the rows of a board must all be as long"
+        );
+
+        Board {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        }
+    }
+
+    // `cells` are the rows of a board of the given dimensions, one after the other
+    pub fn from_cells(cells: Vec<T>, dim: (usize, usize)) -> Self {
+        assert_eq!(
+            cells.len(),
+            dim.0 * dim.1,
+            "wrong number of cells for the board"
+        );
+
+        Board {
+            cells,
+            width: dim.1,
+            height: dim.0,
+        }
+    }
+
+    // Builds a board with a cell per character of `lines`, converted with `f`.
+    // Every row must be as long as the first one.
+    pub fn parse_chars_with<S: AsRef<str>>(
+        lines: &[S],
+        f: impl Fn(char) -> Result<T>,
+    ) -> Result<Self> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+
+        for (i, line) in lines.iter().enumerate() {
+            let before = cells.len();
+            for (j, c) in line.as_ref().chars().enumerate() {
+                cells.push(f(c).map_err(|e| e.at_line(i + 1).at_column(j + 1))?);
+            }
+
+            let len = cells.len() - before;
+            if i == 0 {
+                width = len;
+            } else if len != width {
+                return Err(Error::parse(format!(
+                    "expected a row of {} cells, found {}",
+                    width, len
+                ))
+                .at_line(i + 1)
+                .at_column(len.min(width) + 1));
+            }
+        }
+
+        Ok(Board {
+            cells,
+            width,
+            height: lines.len(),
+        })
     }
 
     pub fn dim(&self) -> (usize, usize) {
-        (self.0.len(), self.0[0].len())
+        (self.height, self.width)
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        if point.0 < self.height && point.1 < self.width {
+            Some(point.0 * self.width + point.1)
+        } else {
+            None
+        }
     }
 
     pub fn get(&self, point: Point) -> Option<&T> {
-        self.0.get(point.0).and_then(|row| row.get(point.1))
+        self.index(point).map(|i| &self.cells[i])
     }
 
     pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
-        self.0.get_mut(point.0).and_then(|row| row.get_mut(point.1))
+        self.index(point).map(move |i| &mut self.cells[i])
     }
 
-    pub fn get_row(&self, row: usize) -> Option<&Vec<T>> {
-        self.0.get(row)
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row < self.height {
+            Some(&self.cells[row * self.width..(row + 1) * self.width])
+        } else {
+            None
+        }
     }
 
-    pub fn expose(&self) -> &Vec<Vec<T>> {
-        &self.0
+    pub fn row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        if row < self.height {
+            Some(&mut self.cells[row * self.width..(row + 1) * self.width])
+        } else {
+            None
+        }
     }
 
-    pub fn get_neighbours(&self, p: &Point, diagonals: bool) -> Vec<Point> {
-        let mut neighbours = vec![];
-        let board = &self.0;
+    // The cells, row by row
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
 
-        if p.0 > 0 {
-            neighbours.push((p.0 - 1, p.1));
+    // The points of all cells, in the same order as `iter`. This doesn't
+    // borrow the board, so it can be changed while walking over it.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
 
-            if diagonals {
-                if p.1 > 0 {
-                    neighbours.push((p.0 - 1, p.1 - 1));
-                }
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.positions().zip(self.iter())
+    }
 
-                if p.1 < board[p.0].len() - 1 {
-                    neighbours.push((p.0 - 1, p.1 + 1));
-                }
-            }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |row| self.row(row).unwrap())
+    }
+
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        if col < self.width {
+            Some(self.cells.iter().skip(col).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |col| self.column(col).unwrap())
+    }
+
+    // The points around `p` (see `Neighbours` for the options)
+    pub fn neighbours<'a>(&self, p: Point, hood: Neighbourhood<'a>) -> Neighbours<'a> {
+        Neighbours::around(p, self.dim(), hood)
+    }
+}
+
+// Offsets are (row, column) and listed in reading order
+const VON_NEUMANN: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, Copy)]
+pub enum Neighbourhood<'a> {
+    // Up, left, right and down
+    VonNeumann,
+    // Also the diagonals
+    Moore,
+    // Any (row, column) offsets
+    Custom(&'a [(isize, isize)]),
+}
+
+impl<'a> Neighbourhood<'a> {
+    fn offsets(self) -> &'a [(isize, isize)] {
+        match self {
+            Neighbourhood::VonNeumann => &VON_NEUMANN,
+            Neighbourhood::Moore => &MOORE,
+            Neighbourhood::Custom(offsets) => offsets,
         }
+    }
+}
 
-        if p.0 < board.len() - 1 {
-            neighbours.push((p.0 + 1, p.1));
+/*
+ Iterates over the neighbours of a point without allocating. Neighbours
+ that fall off the board are skipped, unless the board wraps around
+ (`wrapping`), in which case they are taken from the opposite edge.
+ The point itself can be included with `with_self`, right where it falls
+ among the offsets, so e.g. a Moore neighbourhood with itself walks its
+ 3x3 window in reading order.
 
-            if diagonals {
-                if p.1 < board[p.0].len() - 1 {
-                    neighbours.push((p.0 + 1, p.1 + 1));
-                }
+ It doesn't borrow the board, which can be changed while iterating.
+*/
+#[derive(Debug, Clone)]
+pub struct Neighbours<'a> {
+    center: Point,
+    dim: (usize, usize),
+    offsets: &'a [(isize, isize)],
+    next: usize,
+    include_self: bool,
+    wrap: bool,
+}
 
-                if p.1 > 0 {
-                    neighbours.push((p.0 + 1, p.1 - 1));
-                }
-            }
+impl<'a> Neighbours<'a> {
+    // For anything board-like of the given dimensions
+    pub fn around(p: Point, dim: (usize, usize), hood: Neighbourhood<'a>) -> Self {
+        Neighbours {
+            center: p,
+            dim,
+            offsets: hood.offsets(),
+            next: 0,
+            include_self: false,
+            wrap: false,
         }
+    }
+
+    pub fn wrapping(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    pub fn with_self(mut self) -> Self {
+        self.include_self = true;
+        self
+    }
 
-        if p.1 > 0 {
-            neighbours.push((p.0, p.1 - 1));
+    fn shift(&self, coord: usize, by: isize, len: usize) -> Option<usize> {
+        if self.wrap && len > 0 {
+            Some((coord as isize + by).rem_euclid(len as isize) as usize)
+        } else {
+            coord.checked_add_signed(by).filter(|&c| c < len)
         }
+    }
+}
+
+impl<'a> Iterator for Neighbours<'a> {
+    type Item = Point;
 
-        if p.1 < board[p.0].len() - 1 {
-            neighbours.push((p.0, p.1 + 1));
+    fn next(&mut self) -> Option<Point> {
+        loop {
+            let offset = self.offsets.get(self.next);
+            if self.include_self && offset.is_none_or(|&o| o > (0, 0)) {
+                self.include_self = false;
+                return Some(self.center);
+            }
+
+            let &(row, col) = offset?;
+            self.next += 1;
+
+            let row = self.shift(self.center.0, row, self.dim.0);
+            let col = self.shift(self.center.1, col, self.dim.1);
+            if let (Some(row), Some(col)) = (row, col) {
+                return Some((row, col));
+            }
         }
+    }
+}
 
-        neighbours
+impl<T> Board<T>
+where
+    T: From<u8>,
+{
+    // A board of single digits, e.g. heights or risk levels
+    pub fn parse_digits<S: AsRef<str>>(lines: &[S]) -> Result<Self> {
+        Self::parse_chars_with(lines, |c| digit(c).map(T::from))
     }
 }
 
+pub fn digit(c: char) -> Result<u8> {
+    c.to_digit(10)
+        .map(|d| d as u8)
+        .ok_or_else(|| Error::parse(format!("'{}' is not a digit", c)))
+}
+
 impl<T> Board<T>
 where
     T: Copy,
 {
     pub fn with_defaults(val: T, dim: (usize, usize)) -> Self {
-        let vec = vec![vec![val; dim.1]; dim.0];
-        Board(vec)
+        Self::from_cells(vec![val; dim.0 * dim.1], dim)
     }
 }
 
@@ -90,30 +345,31 @@ where
         let max_x = points.iter().map(|e| e.0).max().unwrap() + 1;
         let max_y = points.iter().map(|e| e.1).max().unwrap() + 1;
 
-        let mut board = vec![vec![empty_val; max_y]; max_x];
+        let mut board = Self::from_cells(vec![empty_val; max_x * max_y], (max_x, max_y));
 
         for point in points {
-            board[point.0][point.1] = full_val.clone();
+            *board.get_mut(*point).unwrap() = full_val.clone();
         }
 
-        Board(board)
+        board
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::Board;
-    use super::Point;
+    use super::{Board, Neighbourhood, Point};
 
     #[test]
     fn test_neighbours_no_diagonals() {
-        let board = Board::<char>(vec![
+        let board = Board::<char>::from(vec![
             vec!['a', 'b', 'c'],
             vec!['d', 'e', 'f'],
             vec!['g', 'h', 'i'],
         ]);
 
-        let neighbours: Vec<Point> = board.get_neighbours(&(1, 1), false);
+        let neighbours: Vec<Point> = board
+            .neighbours((1, 1), Neighbourhood::VonNeumann)
+            .collect();
         let expected_neighbours: Vec<Point> = vec![(0, 1), (1, 0), (1, 2), (2, 1)];
 
         assert_eq!(neighbours.len(), expected_neighbours.len());
@@ -124,13 +380,13 @@ mod test {
 
     #[test]
     fn test_neighbours_diagonals() {
-        let board = Board::<char>(vec![
+        let board = Board::<char>::from(vec![
             vec!['a', 'b', 'c'],
             vec!['d', 'e', 'f'],
             vec!['g', 'h', 'i'],
         ]);
 
-        let neighbours: Vec<Point> = board.get_neighbours(&(1, 1), true);
+        let neighbours: Vec<Point> = board.neighbours((1, 1), Neighbourhood::Moore).collect();
         let expected_neighbours: Vec<Point> = vec![
             (0, 0),
             (0, 1),
@@ -147,4 +403,80 @@ mod test {
             assert!(expected_neighbours.contains(&elem));
         }
     }
+
+    #[test]
+    fn test_neighbourhood_options() {
+        let board = Board::<char>::from(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]);
+        let around = |p: Point, hood| board.neighbours(p, hood).collect::<Vec<Point>>();
+
+        assert_eq!(
+            around((0, 0), Neighbourhood::VonNeumann),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            board
+                .neighbours((0, 0), Neighbourhood::VonNeumann)
+                .wrapping()
+                .collect::<Vec<Point>>(),
+            vec![(1, 0), (0, 2), (0, 1), (1, 0)]
+        );
+        assert_eq!(
+            board
+                .neighbours((0, 1), Neighbourhood::Moore)
+                .with_self()
+                .collect::<Vec<Point>>(),
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+        assert_eq!(
+            around((1, 0), Neighbourhood::Custom(&[(-1, 2), (0, 5)])),
+            vec![(0, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rows() {
+        let board = Board::from_cells(vec![1, 2, 3, 4, 5, 6], (2, 3));
+
+        assert_eq!(board, Board::from(vec![vec![1, 2, 3], vec![4, 5, 6]]));
+        assert_eq!(board.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(board.row(2), None);
+        assert_eq!(board.get((0, 3)), None);
+        assert_eq!(board.rows().count(), 2);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut board = Board::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(board.iter().sum::<i32>(), 21);
+        assert_eq!(
+            board.positions().collect::<Vec<Point>>(),
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+        assert_eq!(board.enumerate().nth(4), Some(((1, 1), &5)));
+
+        let columns: Vec<Vec<i32>> = board.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert!(board.column(3).is_none());
+
+        board.iter_mut().for_each(|cell| *cell *= 2);
+        assert_eq!(board.row(1), Some(&[8, 10, 12][..]));
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        let board = Board::<u32>::parse_digits(&["123", "456"]).unwrap();
+
+        assert_eq!(board.dim(), (2, 3));
+        assert_eq!(board.get((1, 0)), Some(&4));
+
+        let err = Board::<u32>::parse_digits(&["123", "4x6"]).unwrap_err();
+        assert_eq!(err.to_string(), "2:2: 'x' is not a digit");
+
+        let err = Board::<u32>::parse_digits(&["123", "45", "789"]).unwrap_err();
+        assert_eq!(err.to_string(), "2:3: expected a row of 3 cells, found 2");
+
+        let empty: [&str; 0] = [];
+        assert_eq!(Board::<u32>::parse_digits(&empty).unwrap().dim(), (0, 0));
+    }
 }
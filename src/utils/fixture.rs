@@ -0,0 +1,82 @@
+use crate::days;
+use crate::error::{Error, Result};
+use crate::utils::answers::unescape;
+use crate::utils::input::Source;
+use crate::utils::params::Params;
+use std::fs::read_to_string;
+
+/*
+ Test fixtures carry their expected answers in their header, which is
+ skipped when the input is read:
+
+   // expect p1: 226
+   // expect p2: 3509
+
+ Answers that span several lines are written on one, with `\n` for the
+ line breaks (and `\\` for a backslash), as in the answers file.
+*/
+const EXPECT: &str = "// expect p";
+
+// The expected answers of both parts, as far as they are known
+pub fn expectations(content: &str) -> Result<[Option<String>; 2]> {
+    let mut expected = [None, None];
+
+    for (i, line) in content.lines().enumerate() {
+        let header = match line.strip_prefix(EXPECT) {
+            Some(header) => header,
+            None => continue,
+        };
+
+        let malformed = || Error::parse(format!("malformed header '{}'", line)).at_line(i + 1);
+        let (part, answer) = header.split_once(':').ok_or_else(malformed)?;
+        let answer = unescape(answer.trim()).ok_or_else(malformed)?;
+        match part {
+            "1" => expected[0] = Some(answer),
+            "2" => expected[1] = Some(answer),
+            _ => return Err(malformed()),
+        }
+    }
+
+    Ok(expected)
+}
+
+// Solves both parts of the fixture at `path` and checks them against its
+// headers. Parts without an expected answer only need to be solvable (or
+// not solved yet).
+pub fn check(day: u8, path: &str) {
+    let puzzle = days::find(day).unwrap_or_else(|| panic!("{}: there is no day {}", path, day));
+    let content = read_to_string(path).unwrap_or_else(|e| panic!("Cannot read {}: {}", path, e));
+    let expected = expectations(&content).unwrap_or_else(|e| panic!("{}: {}", path, e));
+
+    let source = Source::File(path.to_string());
+    for (part, expected) in expected.iter().enumerate() {
+        let answer = match puzzle.run(part == 1, &source, &Params::default()) {
+            Ok(answer) => answer,
+            Err(Error::Unsolved(_)) if expected.is_none() => continue,
+            Err(e) => panic!("{}", e),
+        };
+
+        if let Some(expected) = expected {
+            assert_eq!(&answer, expected, "{} part {}", path, part + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::expectations;
+
+    #[test]
+    fn test_expectations() {
+        let expected =
+            expectations("// expect p2:  54 \n// a note\nA0016C880162017C3686B18A3D4780");
+
+        assert_eq!(expected.unwrap(), [None, Some("54".to_string())]);
+        assert!(expectations("// expect p3: 1").is_err());
+        assert!(expectations("// expect p1 1").is_err());
+
+        let expected = expectations("// expect p1: #.\\n.#\\\\");
+        assert_eq!(expected.unwrap()[0].as_deref(), Some("#.\n.#\\"));
+        assert!(expectations("// expect p1: \\t").is_err());
+    }
+}
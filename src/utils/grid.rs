@@ -0,0 +1,184 @@
+use crate::utils::board::{Board, Rect};
+use std::collections::HashMap;
+
+// (row, column), like `board::Point`, but signed and unbounded
+pub type Coord = (i64, i64);
+
+/*
+ A sparse grid for when the size of the world isn't known up front (or
+ keeps changing): only cells that were set are stored. It keeps track of
+ the smallest box containing all of them, which only ever grows.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    cells: HashMap<Coord, T>,
+    bounds: Option<Rect<i64>>,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Grid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, p: Coord) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Coord) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Coord) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    // Returns what was at `p` before, if anything
+    pub fn insert(&mut self, p: Coord, val: T) -> Option<T> {
+        self.grow(p);
+        self.cells.insert(p, val)
+    }
+
+    pub fn remove(&mut self, p: Coord) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    // The cell at `p`, set to `T::default()` first if it wasn't set yet
+    pub fn get_or_default(&mut self, p: Coord) -> &mut T
+    where
+        T: Default,
+    {
+        self.grow(p);
+        self.cells.entry(p).or_default()
+    }
+
+    fn grow(&mut self, p: Coord) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(p),
+            None => self.bounds = Some(Rect::around(p)),
+        }
+    }
+
+    // None as long as nothing was ever set
+    pub fn bounds(&self) -> Option<Rect<i64>> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The cells that were set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.cells.iter().map(|(&p, val)| (p, val))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.values()
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    // The board spanning the bounds, with `empty` wherever nothing was set.
+    // Its (0, 0) is the top left corner of the bounds.
+    pub fn to_board(&self, empty: T) -> Board<T> {
+        match self.bounds {
+            Some(bounds) => self.to_board_within(bounds, empty),
+            None => Board::from(vec![]),
+        }
+    }
+
+    // The board spanning `rect` instead, leaving out whatever is outside it
+    pub fn to_board_within(&self, rect: Rect<i64>, empty: T) -> Board<T> {
+        let dim = rect.dim();
+        let mut board = Board::from_cells(vec![empty; dim.0 * dim.1], dim);
+        for (p, val) in self.iter().filter(|&(p, _)| rect.contains(p)) {
+            let at = ((p.0 - rect.min.0) as usize, (p.1 - rect.min.1) as usize);
+            *board.get_mut(at).unwrap() = val.clone();
+        }
+
+        board
+    }
+}
+
+impl<T> From<&Board<T>> for Grid<T>
+where
+    T: Clone,
+{
+    fn from(board: &Board<T>) -> Self {
+        let mut grid = Grid::new();
+        for ((row, col), val) in board.enumerate() {
+            grid.insert((row as i64, col as i64), val.clone());
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Grid;
+    use crate::utils::board::{Board, Rect};
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = Grid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert((2, -3), 'a');
+        grid.insert((-1, 4), 'b');
+        *grid.get_or_default((0, 0)) = 'c';
+
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect {
+                min: (-1, -3),
+                max: (2, 4)
+            })
+        );
+        assert_eq!(grid.bounds().unwrap().dim(), (4, 8));
+        assert_eq!(grid.len(), 3);
+    }
+
+    #[test]
+    fn test_board_conversion() {
+        let mut grid = Grid::new();
+        grid.insert((-1, -1), '#');
+        grid.insert((0, 1), '#');
+
+        let board = grid.to_board('.');
+        assert_eq!(
+            board,
+            Board::from(vec![vec!['#', '.', '.'], vec!['.', '.', '#']])
+        );
+
+        let back = Grid::from(&board);
+        assert_eq!(back.len(), 6);
+        assert_eq!(back.get((1, 2)), Some(&'#'));
+        assert_eq!(back.to_board('.'), board);
+
+        let within = Rect {
+            min: (0, 0),
+            max: (1, 2),
+        };
+        assert_eq!(
+            grid.to_board_within(within, '.'),
+            Board::from(vec![vec!['.', '#', '.'], vec!['.', '.', '.']])
+        );
+    }
+}
@@ -1,34 +1,647 @@
-use std::fs::read_to_string;
+use crate::error::{Error, Result};
+use crate::utils::params::Params;
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::iter::Enumerate;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
 
-pub fn read_file(day: &str, test: bool) -> Vec<String> {
-    read_lines(&inp_file(day, test))
+// Overrides the directory the inputs are looked up in
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+// Where a puzzle's input comes from
+#[derive(Debug, Clone)]
+pub enum Source {
+    // The day's own `dayN.in` (or `test/dayN.in`) in the input directory
+    Default { test: bool },
+    // Any file, e.g. someone else's input or an edge case
+    File(String),
+    // Standard input, read up front so that it can be solved more than once
+    Stdin(Arc<str>),
 }
 
-pub fn convert_to_ints(lines: &[String]) -> Vec<i32> {
-    lines
-        .iter()
-        .map(|s| {
-            s.parse::<i32>()
-                .unwrap_or_else(|_| panic!("{} is not a number", s))
+impl Source {
+    pub fn stdin() -> Result<Self> {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|source| Error::Io {
+                path: "<stdin>".to_string(),
+                source,
+            })?;
+
+        Ok(Source::Stdin(content.into()))
+    }
+
+    // `-` stands for standard input, anything else is a path
+    pub fn from_arg(arg: &str) -> Result<Self> {
+        match arg {
+            "-" => Self::stdin(),
+            path => Ok(Source::File(path.to_string())),
+        }
+    }
+
+    // What to call the input of `day` in messages
+    pub fn describe(&self, day: u8) -> String {
+        match self {
+            Source::Default { test } => inp_file(day, *test),
+            Source::File(path) => path.clone(),
+            Source::Stdin(_) => "<stdin>".to_string(),
+        }
+    }
+
+    // Opens the input of `day`. Nothing is read until the day asks for it.
+    pub fn open(&self, day: u8) -> Result<Input> {
+        let name = self.describe(day);
+
+        match self {
+            Source::Default { .. } | Source::File(_) => {
+                let file = File::open(&name).map_err(|source| Error::Io {
+                    path: name.clone(),
+                    source,
+                })?;
+                Ok(Input::from_reader(BufReader::new(file), &name))
+            }
+            Source::Stdin(content) => Ok(Input::from_reader(
+                Cursor::new(Shared(content.clone())),
+                &name,
+            )),
+        }
+    }
+}
+
+// Lets several readers share the one copy of stdin
+struct Shared(Arc<str>);
+
+impl AsRef<[u8]> for Shared {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+type RawLines = Box<dyn Iterator<Item = Result<String>>>;
+
+/*
+ A day's input. Most days take all of it at once with `lines`. Days that
+ only go over their input once can `stream` it instead (and say so with
+ `Solution::STREAMS`), so that even huge (e.g. generated) inputs never
+ have to be held in memory.
+*/
+pub struct Input {
+    raw: RawLines,
+    comment: Option<&'static str>,
+    numbers: LineNumbers,
+    params: Params,
+}
+
+impl Input {
+    // `name` is what to call the reader in errors
+    pub fn from_reader(reader: impl BufRead + 'static, name: &str) -> Self {
+        let name = name.to_string();
+        let raw = reader.lines().map(move |line| {
+            line.map_err(|source| Error::Io {
+                path: name.clone(),
+                source,
+            })
+        });
+
+        Self {
+            raw: Box::new(raw),
+            comment: None,
+            numbers: LineNumbers::default(),
+            params: Params::default(),
+        }
+    }
+
+    pub fn from_text(text: &str) -> Self {
+        Self::from_reader(Cursor::new(text.to_string()), "<text>")
+    }
+
+    // See `Solution::COMMENT`
+    pub fn with_comment(mut self, comment: Option<&'static str>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    pub fn with_params(mut self, params: Params) -> Self {
+        self.params = params;
+        self
+    }
+
+    // The value of a `--param` the day takes (see `utils::params`), if set
+    pub fn param<T: FromStr>(&self, name: &str) -> Result<Option<T>> {
+        self.params.get(name)
+    }
+
+    // Where the lines returned by `lines` are in the input
+    pub fn numbers(&self) -> LineNumbers {
+        self.numbers.clone()
+    }
+
+    // Reads all of the input into memory, so that nothing is read later on
+    pub fn buffered(self) -> Result<Self> {
+        let raw: Vec<String> = self.raw.collect::<Result<_>>()?;
+
+        Ok(Self {
+            raw: Box::new(raw.into_iter().map(Ok)),
+            ..self
         })
-        .collect()
+    }
+
+    // All of the (cleaned up) lines at once
+    pub fn lines(self) -> Result<Vec<String>> {
+        let mut numbers = self.numbers.0.borrow_mut();
+        let mut lines = Vec::new();
+
+        for line in Cleaned::new(self.raw, self.comment) {
+            let (n, line) = line?;
+            numbers.push(n);
+            lines.push(line);
+        }
+
+        Ok(lines)
+    }
+
+    // The (cleaned up) lines one at a time, as they are read
+    pub fn stream(self) -> Stream {
+        Cleaned::new(self.raw, self.comment)
+    }
+}
+
+pub type Stream = Cleaned<RawLines>;
+
+/*
+ Days number the lines they get from `Input::lines` by their position,
+ which is off whenever lines were skipped (see `Cleaned`). This maps those
+ positions back to the actual line numbers. Streamed lines come with their
+ actual line numbers already, so nothing is mapped for them.
+*/
+#[derive(Clone, Default)]
+pub struct LineNumbers(Rc<RefCell<Vec<usize>>>);
+
+impl LineNumbers {
+    pub fn get(&self, n: usize) -> usize {
+        self.0.borrow().get(n.wrapping_sub(1)).copied().unwrap_or(n)
+    }
 }
 
-fn read_lines(input: &str) -> Vec<String> {
-    let content =
-        read_to_string(input).unwrap_or_else(|_| panic!("Put something in {} first", input));
+// Fixtures start with a header of annotations (see `utils::fixture`). No
+// puzzle input starts with this, so the header can always be skipped.
+pub const HEADER: &str = "//";
+
+/*
+ Turns the raw lines of an input into what days get to see:
+   - the header (if any) is skipped
+   - so are lines starting with the day's comment prefix, for days that
+     have one. Comments are opt-in since what is a comment for one day
+     is data for another (e.g. '#' in day 13's dots).
+   - trailing whitespace, including the '\r' of CRLF endings, is dropped
+   - blank lines at the very end are dropped altogether
+ Every line comes with its (1-based) line number in the input, which
+ skipped lines would otherwise throw off.
+*/
+pub struct Cleaned<I> {
+    raw: Enumerate<I>,
+    comment: Option<&'static str>,
+    in_header: bool,
+    // Blank lines are held back until we know they aren't the trailing ones
+    blanks: Vec<usize>,
+    ready: VecDeque<(usize, String)>,
+}
+
+impl<I> Cleaned<I>
+where
+    I: Iterator<Item = Result<String>>,
+{
+    pub fn new(raw: I, comment: Option<&'static str>) -> Self {
+        Self {
+            raw: raw.enumerate(),
+            comment,
+            in_header: true,
+            blanks: Vec::new(),
+            ready: VecDeque::new(),
+        }
+    }
+}
+
+impl<I> Iterator for Cleaned<I>
+where
+    I: Iterator<Item = Result<String>>,
+{
+    type Item = Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.ready.is_empty() {
+            let (i, line) = self.raw.next()?;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            let line = line.trim_end();
+
+            self.in_header = self.in_header && line.starts_with(HEADER);
+            if self.in_header || self.comment.is_some_and(|c| line.starts_with(c)) {
+                continue;
+            }
+
+            if line.is_empty() {
+                self.blanks.push(i + 1);
+                continue;
+            }
 
-    content
-        .split('\n')
-        .map(|s| s.to_string())
-        .filter(|s| !s.starts_with('#'))
+            let blanks = self.blanks.drain(..).map(|n| (n, String::new()));
+            self.ready.extend(blanks);
+            self.ready.push_back((i + 1, line.to_string()));
+        }
+
+        self.ready.pop_front().map(Ok)
+    }
+}
+
+// Parses a number, reporting the offending text if it isn't one
+pub fn parse_number<T: FromStr>(s: &str) -> Result<T> {
+    s.parse::<T>()
+        .map_err(|_| Error::parse(format!("'{}' is not a valid number", s)))
+}
+
+// The 1-based column at which `token` (which must be a slice of `line`) starts
+pub fn column_of(line: &str, token: &str) -> usize {
+    token.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+/*
+ Small parsers for the shapes puzzle lines come in. They take a line (or
+ a part of one) and point their errors at the column things went wrong,
+ so the caller only has to add the line number. They compose, e.g.
+
+   arrow_pair(line, |s| pair(s, ",", parse_number, parse_number))
+
+ parses `0,9 -> 5,9`.
+*/
+
+// Runs `parse` on `token` (a slice of `line`) so that its errors point
+// into `line`. Errors without a column point at the start of `token`.
+pub fn parse_at<'a, T>(
+    line: &str,
+    token: &'a str,
+    parse: impl FnOnce(&'a str) -> Result<T>,
+) -> Result<T> {
+    parse(token).map_err(|e| e.at_column(1).shift_column(column_of(line, token) - 1))
+}
+
+// `1,2,3`
+pub fn comma_list<T: FromStr>(line: &str) -> Result<Vec<T>> {
+    separated(line, ",", parse_number)
+}
+
+// Every `sep` separated part of `line`, each parsed with `parse`
+pub fn separated<'a, T>(
+    line: &'a str,
+    sep: &str,
+    parse: impl Fn(&'a str) -> Result<T>,
+) -> Result<Vec<T>> {
+    line.split(sep)
+        .map(|token| parse_at(line, token, &parse))
         .collect()
 }
 
-fn inp_file(day: &str, test: bool) -> String {
+// Exactly two `sep` separated parts of `line`, e.g. `3,4` or `start-end`
+pub fn pair<'a, A, B>(
+    line: &'a str,
+    sep: &str,
+    left: impl FnOnce(&'a str) -> Result<A>,
+    right: impl FnOnce(&'a str) -> Result<B>,
+) -> Result<(A, B)> {
+    match line.split_once(sep) {
+        Some((l, r)) if !r.contains(sep) => {
+            Ok((parse_at(line, l, left)?, parse_at(line, r, right)?))
+        }
+        _ => Err(Error::parse(format!(
+            "expected two parts separated by '{}', found '{}'",
+            sep, line
+        ))),
+    }
+}
+
+// `a -> b`
+pub fn arrow_pair<'a, A, B>(
+    line: &'a str,
+    left: impl FnOnce(&'a str) -> Result<A>,
+    right: impl FnOnce(&'a str) -> Result<B>,
+) -> Result<(A, B)> {
+    pair(line, " -> ", left, right)
+}
+
+// `key=value`, with the key as is and the value parsed with `parse`
+pub fn key_value<'a, T>(
+    line: &'a str,
+    parse: impl FnOnce(&'a str) -> Result<T>,
+) -> Result<(&'a str, T)> {
+    pair(
+        line,
+        "=",
+        |key| Ok(key.trim()),
+        |value| parse_at(value, value.trim(), parse),
+    )
+}
+
+// The whitespace separated fields of `line`, of which there must be `N`
+pub fn fields<const N: usize>(line: &str) -> Result<[&str; N]> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let count = fields.len();
+
+    fields.try_into().map_err(|_| {
+        Error::parse(format!(
+            "expected {} whitespace separated fields, found {}",
+            N, count
+        ))
+    })
+}
+
+// A regex whose named groups can be extracted as typed values
+pub struct Pattern {
+    regex: Regex,
+    // What a matching line looks like, for error messages
+    expected: &'static str,
+}
+
+impl Pattern {
+    pub fn new(regex: &str, expected: &'static str) -> Self {
+        Self {
+            regex: Regex::new(regex).unwrap_or_else(|e| panic!("Invalid pattern {}: {}", regex, e)),
+            expected,
+        }
+    }
+
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Captures<'a>> {
+        match self.regex.captures(line) {
+            Some(captures) => Ok(Captures { line, captures }),
+            None => Err(Error::parse(format!(
+                "expected {}, found '{}'",
+                self.expected, line
+            ))),
+        }
+    }
+}
+
+pub struct Captures<'a> {
+    line: &'a str,
+    captures: regex::Captures<'a>,
+}
+
+impl<'a> Captures<'a> {
+    pub fn str(&self, name: &str) -> Result<&'a str> {
+        self.captures
+            .name(name)
+            .map(|m| m.as_str())
+            .ok_or_else(|| Error::parse(format!("'{}' has no {}", self.line, name)))
+    }
+
+    pub fn get<T>(&self, name: &str, parse: impl FnOnce(&'a str) -> Result<T>) -> Result<T> {
+        parse_at(self.line, self.str(name)?, parse)
+    }
+}
+
+pub fn first_line(lines: &[String]) -> Result<&str> {
+    lines
+        .first()
+        .map(|l| &l[..])
+        .ok_or_else(|| Error::parse("the input is empty"))
+}
+
+// A run of non-blank lines of the input, as in puzzles made of several
+// blank-line separated parts (e.g. the draws and the boards of day 4)
+#[derive(Debug, PartialEq)]
+pub struct Section<'a> {
+    // The 1-based line number of the section's first line
+    pub start: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    // Every line along with its (1-based) line number in the input
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(move |(i, l)| (self.start + i, *l))
+    }
+
+    // The section's only line, for sections that must consist of one
+    pub fn single_line(&self) -> Result<&'a str> {
+        match self.lines[..] {
+            [line] => Ok(line),
+            _ => Err(Error::parse(format!(
+                "expected a single line, found {}",
+                self.lines.len()
+            ))
+            .at_line(self.start)),
+        }
+    }
+}
+
+/*
+ Splits the input into its blank-line separated sections. Any number of
+ blank lines separates two sections, leading and trailing ones are ignored
+ and so are CRLF line endings.
+*/
+pub fn sections(lines: &[String]) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut current: Option<Section> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            sections.extend(current.take());
+            continue;
+        }
+
+        current
+            .get_or_insert_with(|| Section {
+                start: i + 1,
+                lines: Vec::new(),
+            })
+            .lines
+            .push(line);
+    }
+
+    sections.extend(current);
+    sections
+}
+
+// Like `sections`, but there must be exactly `N` of them
+pub fn sections_exactly<const N: usize>(lines: &[String]) -> Result<[Section<'_>; N]> {
+    let found = sections(lines);
+    let count = found.len();
+
+    found.try_into().map_err(|_| {
+        Error::parse(format!(
+            "expected {} blank-line separated sections, found {}",
+            N, count
+        ))
+    })
+}
+
+/*
+ The directory holding the inputs. In order of preference:
+   - whatever $AOC_INPUT_DIR points to
+   - `input` in the current directory
+   - the repository's own `input`, so the binary can be run from anywhere
+*/
+pub fn input_dir() -> PathBuf {
+    if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+        return PathBuf::from(dir);
+    }
+
+    let local = Path::new("input");
+    if local.is_dir() {
+        return local.to_path_buf();
+    }
+
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input")
+}
+
+pub fn inp_file(day: u8, test: bool) -> String {
+    let dir = input_dir();
+    let file = format!("day{}.in", day);
+
     if test {
-        format!("input/test/day{}.in", day)
+        dir.join("test").join(file)
     } else {
-        format!("input/day{}.in", day)
+        dir.join(file)
+    }
+    .display()
+    .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        arrow_pair, comma_list, fields, key_value, pair, parse_number, sections, sections_exactly,
+        Input, Pattern, Result, Source,
+    };
+
+    #[test]
+    fn test_stdin_source() {
+        let source = Source::Stdin("1\n2".into());
+
+        // Every part gets to read all of it
+        assert_eq!(source.open(1).unwrap().lines().unwrap(), vec!["1", "2"]);
+        assert_eq!(source.open(1).unwrap().lines().unwrap(), vec!["1", "2"]);
+        assert_eq!(source.describe(1), "<stdin>");
+    }
+
+    #[test]
+    fn test_buffered() {
+        let input = Input::from_text("// header\na\n\nb\n").buffered().unwrap();
+        let numbers = input.numbers();
+
+        assert_eq!(input.lines().unwrap(), vec!["a", "", "b"]);
+        assert_eq!(numbers.get(3), 4);
+    }
+
+    fn numbered(lines: &[(usize, &str)]) -> Vec<(usize, String)> {
+        lines.iter().map(|(n, l)| (*n, l.to_string())).collect()
+    }
+
+    #[test]
+    fn test_cleaned() {
+        let content = "// expect p1: 1\n//\n#.#  \r\n\n; note\n##\r\n\n\n";
+        let stream = |comment| {
+            Input::from_text(content)
+                .with_comment(comment)
+                .stream()
+                .collect::<Result<Vec<_>>>()
+                .unwrap()
+        };
+
+        assert_eq!(
+            stream(None),
+            numbered(&[(3, "#.#"), (4, ""), (5, "; note"), (6, "##")])
+        );
+        assert_eq!(
+            stream(Some(";")),
+            numbered(&[(3, "#.#"), (4, ""), (6, "##")])
+        );
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let input = Input::from_text("// header\n\na\nb");
+        let numbers = input.numbers();
+
+        assert_eq!(input.lines().unwrap(), vec!["", "a", "b"]);
+        assert_eq!((numbers.get(1), numbers.get(3)), (2, 4));
+    }
+
+    #[test]
+    fn test_sections() {
+        let lines: Vec<String> = "\n1,2\r\n\r\na\nb\n\n \n\nc\n\n"
+            .split('\n')
+            .map(|l| l.to_string())
+            .collect();
+        let found = sections(&lines);
+
+        assert_eq!(found.len(), 3);
+        assert_eq!((found[0].start, &found[0].lines[..]), (2, &["1,2"][..]));
+        assert_eq!((found[1].start, &found[1].lines[..]), (4, &["a", "b"][..]));
+        assert_eq!(found[1].numbered().last(), Some((5, "b")));
+        assert_eq!(found[2].single_line().unwrap(), "c");
+        assert!(found[1].single_line().is_err());
+
+        assert!(sections_exactly::<3>(&lines).is_ok());
+        assert!(sections_exactly::<2>(&lines).is_err());
+    }
+
+    #[test]
+    fn test_combinators() {
+        let point = |s| pair(s, ",", parse_number::<u32>, parse_number::<u32>);
+
+        assert_eq!(comma_list::<i32>("3,-4,5").unwrap(), vec![3, -4, 5]);
+        assert_eq!(
+            arrow_pair("0,9 -> 5,9", point, point).unwrap(),
+            ((0, 9), (5, 9))
+        );
+        assert_eq!(key_value("x = 5", parse_number::<u8>).unwrap(), ("x", 5));
+        assert_eq!(fields::<2>("forward  5").unwrap(), ["forward", "5"]);
+
+        let caps = Pattern::new(r"^(?P<name>\w+) is (?P<age>\d+)$", "an age").captures("bob is 7");
+        assert_eq!(caps.unwrap().get("age", parse_number::<u8>).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_combinator_errors() {
+        let point = |s| pair(s, ",", parse_number::<u32>, parse_number::<u32>);
+        fn error<T>(res: Result<T>) -> String {
+            res.err().unwrap().at_line(1).to_string()
+        }
+
+        assert_eq!(
+            error(comma_list::<i32>("1,x,3")),
+            "1:3: 'x' is not a valid number"
+        );
+        assert_eq!(
+            error(arrow_pair("0,9 -> 5,y", point, point)),
+            "1:10: 'y' is not a valid number"
+        );
+        assert_eq!(
+            error(point("1,2,3")),
+            "1: expected two parts separated by ',', found '1,2,3'"
+        );
+        assert_eq!(
+            error(fields::<2>("a b c")),
+            "1: expected 2 whitespace separated fields, found 3"
+        );
+        assert_eq!(
+            error(Pattern::new(r"^\d+$", "a number").captures("x")),
+            "1: expected a number, found 'x'"
+        );
     }
 }
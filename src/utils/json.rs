@@ -0,0 +1,89 @@
+use std::fmt;
+
+// Just enough of JSON to serialize our own output
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_string())
+    }
+}
+
+impl<T> From<Option<T>> for Json
+where
+    T: Into<Json>,
+{
+    fn from(opt: Option<T>) -> Self {
+        opt.map_or(Json::Null, Into::into)
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Int(i) => write!(f, "{}", i),
+            // JSON has no representation for NaN or infinities
+            Json::Float(x) if !x.is_finite() => write!(f, "null"),
+            Json::Float(x) => write!(f, "{}", x),
+            Json::String(s) => write_string(f, s),
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+#[cfg(test)]
+mod test {
+    use super::Json;
+
+    #[test]
+    fn test_serialize() {
+        let json = Json::Object(vec![
+            ("day", Json::Int(13)),
+            ("answer", Json::from("#.\n\"x\"\u{1}")),
+            ("error", Json::from(None::<&str>)),
+            ("ok", Json::Bool(true)),
+            ("ms", Json::Float(1.5)),
+            ("nan", Json::Float(f64::NAN)),
+        ]);
+
+        assert_eq!(
+            json.to_string(),
+            r##"{"day":13,"answer":"#.\n\"x\"\u0001","error":null,"ok":true,"ms":1.5,"nan":null}"##
+        );
+    }
+}
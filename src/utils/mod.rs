@@ -1,2 +1,13 @@
+pub mod answers;
 pub mod board;
+#[cfg(test)]
+pub mod fixture;
+pub mod grid;
 pub mod input;
+pub mod json;
+pub mod params;
+pub mod region;
+pub mod render;
+pub mod search;
+pub mod table;
+pub mod transform;
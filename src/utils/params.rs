@@ -0,0 +1,118 @@
+use crate::error::{Error, Result};
+use crate::solution::Puzzle;
+use crate::utils::input::{key_value, parse_number};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/*
+ Settings for days that can solve more than the puzzle asks for (e.g. a
+ larger version of it), given on the command line as `--param name=value`.
+ Days list the ones they take in `Solution::PARAMS` and get them with their
+ input (see `Input::param`), falling back to the puzzle's own values for
+ whatever isn't set.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params(HashMap<String, String>);
+
+impl Params {
+    // Every `arg` is a `name=value`
+    pub fn parse<'a>(args: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut params = Params::default();
+
+        for arg in args {
+            let (name, value) = key_value(arg, Ok)?;
+            if name.is_empty() {
+                return Err(Error::parse(format!("'{}' has no parameter name", arg)));
+            }
+            params.set(name, value);
+        }
+
+        Ok(params)
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>> {
+        match self.0.get(name) {
+            Some(value) => parse_number(value)
+                .map(Some)
+                .map_err(|e| Error::param(name, e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    // Every parameter must be taken by at least one of the `puzzles`, so
+    // that a misspelt one isn't silently left out
+    pub fn check(&self, puzzles: &[&dyn Puzzle]) -> Result<()> {
+        let mut known: Vec<&str> = puzzles.iter().flat_map(|p| p.params()).copied().collect();
+        known.sort_unstable();
+        known.dedup();
+
+        let mut unknown: Vec<&String> = self
+            .0
+            .keys()
+            .filter(|name| !known.contains(&&name[..]))
+            .collect();
+        unknown.sort();
+
+        match (unknown.first(), &known[..]) {
+            (None, _) => Ok(()),
+            (Some(name), []) => Err(Error::parse(format!(
+                "unknown parameter '{}', no day being solved takes any",
+                name
+            ))),
+            (Some(name), known) => Err(Error::parse(format!(
+                "unknown parameter '{}', the days being solved take: {}",
+                name,
+                known.join(", ")
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Params;
+    use crate::days;
+
+    #[test]
+    fn test_parse() {
+        let params = Params::parse(vec!["tiles=50", "tiles = 7"]).unwrap();
+        assert_eq!(params.get::<usize>("tiles").unwrap(), Some(7));
+        assert_eq!(params.get::<usize>("size").unwrap(), None);
+
+        assert!(Params::parse(vec!["tiles"]).is_err());
+        assert!(Params::parse(vec!["=50"]).is_err());
+        assert!(Params::parse(vec!["tiles=x"])
+            .unwrap()
+            .get::<usize>("tiles")
+            .is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let params = Params::parse(vec!["tile=50"]).unwrap();
+
+        assert_eq!(
+            params.check(days::all()).unwrap_err().to_string(),
+            "unknown parameter 'tile', the days being solved take: tiles"
+        );
+        assert_eq!(
+            params
+                .check(&[days::find(1).unwrap()])
+                .unwrap_err()
+                .to_string(),
+            "unknown parameter 'tile', no day being solved takes any"
+        );
+        assert!(Params::parse(vec!["tiles=50"])
+            .unwrap()
+            .check(days::all())
+            .is_ok());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::utils::board::{Board, Neighbourhood, Point, Rect};
+use std::collections::VecDeque;
+
+pub type RegionId = usize;
+
+/*
+ The connected regions of a board: every cell that belongs to a region is
+ labelled with its id, the rest with None. Ids are handed out in reading
+ order of the regions' first cells and index `sizes` and `bounds`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regions {
+    pub labels: Board<Option<RegionId>>,
+    pub sizes: Vec<usize>,
+    pub bounds: Vec<Rect>,
+}
+
+impl Regions {
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+
+    pub fn of(&self, p: Point) -> Option<RegionId> {
+        self.labels.get(p).copied().flatten()
+    }
+
+    pub fn touches_border(&self, id: RegionId) -> bool {
+        let (height, width) = self.labels.dim();
+        let bounds = self.bounds[id];
+
+        bounds.min.0 == 0
+            || bounds.min.1 == 0
+            || bounds.max.0 + 1 == height
+            || bounds.max.1 + 1 == width
+    }
+}
+
+impl<T> Board<T> {
+    // The points connected to `start` through cells that `belongs` accepts,
+    // in the order they were reached. Empty if `start` itself isn't accepted.
+    pub fn flood_fill(
+        &self,
+        start: Point,
+        hood: Neighbourhood,
+        belongs: impl Fn(&T) -> bool,
+    ) -> Vec<Point> {
+        let mut seen = Board::with_defaults(false, self.dim());
+        self.fill(start, hood, &belongs, &mut seen)
+    }
+
+    // Labels the regions of connected cells that `belongs` accepts
+    pub fn regions(&self, hood: Neighbourhood, belongs: impl Fn(&T) -> bool) -> Regions {
+        let mut seen = Board::with_defaults(false, self.dim());
+        let mut labels = Board::with_defaults(None, self.dim());
+        let mut sizes = Vec::new();
+        let mut bounds = Vec::new();
+
+        for start in self.positions() {
+            let region = self.fill(start, hood, &belongs, &mut seen);
+            if region.is_empty() {
+                continue;
+            }
+
+            let id = sizes.len();
+            let mut rect = Rect::around(start);
+            for &p in region.iter() {
+                *labels.get_mut(p).unwrap() = Some(id);
+                rect.extend(p);
+            }
+            sizes.push(region.len());
+            bounds.push(rect);
+        }
+
+        Regions {
+            labels,
+            sizes,
+            bounds,
+        }
+    }
+
+    // Flood fills from `start`, skipping (and marking) what was `seen` before
+    fn fill(
+        &self,
+        start: Point,
+        hood: Neighbourhood,
+        belongs: &impl Fn(&T) -> bool,
+        seen: &mut Board<bool>,
+    ) -> Vec<Point> {
+        let accepts =
+            |p: Point, seen: &Board<bool>| !seen.get(p).unwrap() && belongs(self.get(p).unwrap());
+        if self.get(start).is_none() || !accepts(start, seen) {
+            return vec![];
+        }
+
+        let mut filled = vec![];
+        let mut queue = VecDeque::from(vec![start]);
+        *seen.get_mut(start).unwrap() = true;
+
+        while let Some(p) = queue.pop_front() {
+            filled.push(p);
+            for n in self.neighbours(p, hood) {
+                if accepts(n, seen) {
+                    *seen.get_mut(n).unwrap() = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        filled
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::utils::board::{Board, Neighbourhood, Rect};
+
+    #[test]
+    fn test_regions() {
+        let lines = ["#...#", "#..#.", ".#...", "....."];
+        let board = Board::parse_chars_with(&lines, |c| Ok(c == '#')).unwrap();
+
+        let regions = board.regions(Neighbourhood::VonNeumann, |&wall| wall);
+        assert_eq!(regions.sizes, vec![2, 1, 1, 1]);
+        assert_eq!(regions.of((2, 1)), Some(3));
+        assert_eq!(regions.of((0, 1)), None);
+        assert_eq!(
+            regions.bounds[0],
+            Rect {
+                min: (0, 0),
+                max: (1, 0)
+            }
+        );
+        assert!(regions.touches_border(1));
+        assert!(!regions.touches_border(2));
+
+        // Diagonals join them in pairs
+        let regions = board.regions(Neighbourhood::Moore, |&wall| wall);
+        assert_eq!(regions.sizes, vec![3, 2]);
+
+        let filled = board.flood_fill((0, 1), Neighbourhood::VonNeumann, |&wall| !wall);
+        assert_eq!(filled.len(), 15);
+        assert_eq!(filled[..3], [(0, 1), (0, 2), (1, 1)]);
+        assert!(board
+            .flood_fill((0, 0), Neighbourhood::VonNeumann, |&wall| !wall)
+            .is_empty());
+    }
+}
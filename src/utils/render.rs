@@ -0,0 +1,206 @@
+use crate::utils::board::{Board, Point};
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{self, Write};
+
+pub type Rgb = (u8, u8, u8);
+
+pub const WHITE: Rgb = (255, 255, 255);
+pub const RED: Rgb = (255, 0, 0);
+
+/*
+ Draws a board, one character (and colour) per cell. The characters are
+ used for text, the colours for images and the terminal. Highlighted
+ points, e.g. a path through the board, are drawn in their own colour
+ (and character, if one is set).
+
+   board
+       .render(|&risk| char::from(b'0' + risk as u8))
+       .colours(|&risk| grey(risk as u8 * 28))
+       .highlight(path)
+       .to_ansi()
+*/
+pub struct Renderer<'a, T> {
+    board: &'a Board<T>,
+    glyph: Box<dyn Fn(&T) -> char + 'a>,
+    colour: Box<dyn Fn(&T) -> Rgb + 'a>,
+    highlighted: HashSet<Point>,
+    highlight_glyph: Option<char>,
+    highlight_colour: Rgb,
+}
+
+impl<T> Board<T> {
+    pub fn render<'a>(&'a self, glyph: impl Fn(&T) -> char + 'a) -> Renderer<'a, T> {
+        Renderer {
+            board: self,
+            glyph: Box::new(glyph),
+            colour: Box::new(|_| WHITE),
+            highlighted: HashSet::new(),
+            highlight_glyph: None,
+            highlight_colour: RED,
+        }
+    }
+}
+
+impl<'a, T> Renderer<'a, T> {
+    pub fn colours(mut self, colour: impl Fn(&T) -> Rgb + 'a) -> Self {
+        self.colour = Box::new(colour);
+        self
+    }
+
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>) -> Self {
+        self.highlighted.extend(points);
+        self
+    }
+
+    pub fn highlight_with(mut self, glyph: Option<char>, colour: Rgb) -> Self {
+        self.highlight_glyph = glyph;
+        self.highlight_colour = colour;
+        self
+    }
+
+    fn glyph_at(&self, p: Point, cell: &T) -> char {
+        match self.highlight_glyph {
+            Some(glyph) if self.highlighted.contains(&p) => glyph,
+            _ => (self.glyph)(cell),
+        }
+    }
+
+    fn colour_at(&self, p: Point, cell: &T) -> Rgb {
+        if self.highlighted.contains(&p) {
+            self.highlight_colour
+        } else {
+            (self.colour)(cell)
+        }
+    }
+
+    // One line per row, without a trailing newline
+    pub fn to_text(&self) -> String {
+        self.lines(|p, cell| self.glyph_at(p, cell).to_string(), "")
+    }
+
+    // Like `to_text`, with every cell in its colour (24-bit ANSI escapes)
+    pub fn to_ansi(&self) -> String {
+        let cell = |p, cell: &T| {
+            let (r, g, b) = self.colour_at(p, cell);
+            format!("\x1b[38;2;{};{};{}m{}", r, g, b, self.glyph_at(p, cell))
+        };
+        self.lines(cell, "\x1b[0m")
+    }
+
+    fn lines(&self, cell: impl Fn(Point, &T) -> String, row_end: &str) -> String {
+        let mut text = String::new();
+
+        for (i, row) in self.board.rows().enumerate() {
+            if i > 0 {
+                text.push('\n');
+            }
+            for (j, val) in row.iter().enumerate() {
+                text.push_str(&cell((i, j), val));
+            }
+            text.push_str(row_end);
+        }
+
+        text
+    }
+
+    // A binary PPM image with a pixel per cell
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        let (height, width) = self.board.dim();
+        write!(out, "P6\n{} {}\n255\n", width, height)?;
+
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for (p, cell) in self.board.enumerate() {
+            let (r, g, b) = self.colour_at(p, cell);
+            pixels.extend_from_slice(&[r, g, b]);
+        }
+        out.write_all(&pixels)
+    }
+
+    // A binary PGM image with a pixel per cell, using the colours' luminance
+    pub fn write_pgm(&self, out: &mut impl Write) -> io::Result<()> {
+        let (height, width) = self.board.dim();
+        write!(out, "P5\n{} {}\n255\n", width, height)?;
+
+        let pixels: Vec<u8> = self
+            .board
+            .enumerate()
+            .map(|(p, cell)| luminance(self.colour_at(p, cell)))
+            .collect();
+        out.write_all(&pixels)
+    }
+}
+
+pub fn grey(level: u8) -> Rgb {
+    (level, level, level)
+}
+
+fn luminance((r, g, b): Rgb) -> u8 {
+    ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+// Cells as they display, rows on lines of their own
+impl<T> fmt::Display for Board<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{grey, RED};
+    use crate::utils::board::Board;
+
+    #[test]
+    fn test_text() {
+        let board = Board::from(vec![vec![1, 0, 1], vec![0, 1, 0]]);
+
+        assert_eq!(board.to_string(), "101\n010");
+        assert_eq!(
+            board
+                .render(|&c| if c == 1 { '#' } else { '.' })
+                .highlight(vec![(1, 0)])
+                .highlight_with(Some('*'), RED)
+                .to_text(),
+            "#.#\n*#."
+        );
+    }
+
+    #[test]
+    fn test_ansi() {
+        let board = Board::from(vec![vec!['a'], vec!['b']]);
+        let ansi = board
+            .render(|&c| c)
+            .colours(|_| grey(7))
+            .highlight(vec![(1, 0)])
+            .to_ansi();
+
+        assert_eq!(ansi, "\x1b[38;2;7;7;7ma\x1b[0m\n\x1b[38;2;255;0;0mb\x1b[0m");
+    }
+
+    #[test]
+    fn test_images() {
+        let board = Board::from(vec![vec![0u8, 255]]);
+        let renderer = board.render(|_| ' ').colours(|&v| grey(v));
+
+        let mut ppm = Vec::new();
+        renderer.write_ppm(&mut ppm).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x00\x00\x00\xff\xff\xff");
+
+        let mut pgm = Vec::new();
+        renderer.write_pgm(&mut pgm).unwrap();
+        assert_eq!(pgm, b"P5\n2 1\n255\n\x00\xff");
+    }
+}
@@ -0,0 +1,318 @@
+use crate::utils::board::{Board, Point};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/*
+ Shortest path searches over any graph: nodes can be board points, cave
+ names or whole states, as long as they can be hashed. The graph is given
+ by a function listing the nodes next to a node, e.g.
+
+   |&p| board.neighbours(p, Neighbourhood::VonNeumann)
+   |cave| graph[cave].iter().cloned()
+
+ Searches may start from several nodes at once, and stop at the first
+ node that satisfies the goal. They return None if no goal can be reached.
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Found<N, C> {
+    pub cost: C,
+    // From the start to the goal, both included
+    pub path: Vec<N>,
+}
+
+// Nodes seen so far, numbered in the order they were first seen, so the
+// rest of the bookkeeping can be kept in plain Vecs
+struct Seen<N, C> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    parents: Vec<Option<usize>>,
+    costs: Vec<C>,
+}
+
+impl<N, C> Seen<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy,
+{
+    fn new() -> Self {
+        Seen {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            parents: Vec::new(),
+            costs: Vec::new(),
+        }
+    }
+
+    // Returns the node's number and whether it's the first time it's seen
+    fn add(&mut self, node: N, parent: Option<usize>, cost: C) -> (usize, bool) {
+        if let Some(&i) = self.index.get(&node) {
+            return (i, false);
+        }
+
+        let i = self.nodes.len();
+        self.index.insert(node.clone(), i);
+        self.nodes.push(node);
+        self.parents.push(parent);
+        self.costs.push(cost);
+        (i, true)
+    }
+
+    fn found(&self, goal: usize) -> Found<N, C> {
+        let mut path = vec![self.nodes[goal].clone()];
+        let mut curr = goal;
+        while let Some(parent) = self.parents[curr] {
+            path.push(self.nodes[parent].clone());
+            curr = parent;
+        }
+        path.reverse();
+
+        Found {
+            cost: self.costs[goal],
+            path,
+        }
+    }
+}
+
+// Breadth-first search, where every step costs 1
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = Seen::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        let (i, new) = seen.add(start, None, 0);
+        if new {
+            queue.push_back(i);
+        }
+    }
+
+    while let Some(curr) = queue.pop_front() {
+        if is_goal(&seen.nodes[curr]) {
+            return Some(seen.found(curr));
+        }
+
+        let cost = seen.costs[curr] + 1;
+        for next in neighbours(&seen.nodes[curr]) {
+            let (i, new) = seen.add(next, Some(curr), cost);
+            if new {
+                queue.push_back(i);
+            }
+        }
+    }
+
+    None
+}
+
+// Cheapest path, where `cost` is what moving from a node to a neighbour costs
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    cost: impl FnMut(&N, &N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = N>,
+{
+    astar(starts, neighbours, cost, |_| C::default(), is_goal)
+}
+
+/*
+ Like `dijkstra`, but looks at the nodes that seem closer to a goal first.
+ `heuristic` estimates the cost from a node to the nearest goal, and must
+ never overestimate it (e.g. the Manhattan distance on a board where every
+ step costs at least 1), otherwise the path found might not be the cheapest.
+*/
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut cost: impl FnMut(&N, &N) -> C,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = Seen::new();
+    // Lowest estimate first, ties go to the node that got further already
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        let (i, new) = seen.add(start, None, C::default());
+        if new {
+            queue.push(Reverse((
+                heuristic(&seen.nodes[i]),
+                Reverse(C::default()),
+                i,
+            )));
+        }
+    }
+
+    while let Some(Reverse((_, Reverse(so_far), curr))) = queue.pop() {
+        // A cheaper way here was already found (and expanded)
+        if so_far > seen.costs[curr] {
+            continue;
+        }
+
+        let node = seen.nodes[curr].clone();
+        if is_goal(&node) {
+            return Some(seen.found(curr));
+        }
+
+        for next in neighbours(&node) {
+            let total = so_far + cost(&node, &next);
+            let estimate = heuristic(&next);
+            let (i, new) = seen.add(next, Some(curr), total);
+
+            if new || total < seen.costs[i] {
+                seen.costs[i] = total;
+                seen.parents[i] = Some(curr);
+                queue.push(Reverse((total + estimate, Reverse(total), i)));
+            }
+        }
+    }
+
+    None
+}
+
+/*
+ `dijkstra` for the points of a board of dimensions `dim`, for boards too
+ large for its bookkeeping. The cost of every point is kept in a board of
+ its own (along with whether it was reached), and the path is traced back
+ from the goal through the neighbours whose cost adds up, instead of
+ keeping the parent of every point. That only works out when neighbours go
+ both ways and every step costs more than nothing, as on a board.
+*/
+pub fn dijkstra_dense<C, I>(
+    dim: (usize, usize),
+    starts: impl IntoIterator<Item = Point>,
+    mut neighbours: impl FnMut(&Point) -> I,
+    mut cost: impl FnMut(&Point, &Point) -> C,
+    mut is_goal: impl FnMut(&Point) -> bool,
+) -> Option<Found<Point, C>>
+where
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = Point>,
+{
+    let mut costs = Board::with_defaults(C::default(), dim);
+    let mut reached = Board::with_defaults(false, dim);
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        *reached.get_mut(start).unwrap() = true;
+        queue.push(Reverse((C::default(), start)));
+    }
+
+    while let Some(Reverse((so_far, curr))) = queue.pop() {
+        // A cheaper way here was already found (and expanded)
+        if so_far > *costs.get(curr).unwrap() {
+            continue;
+        }
+
+        if is_goal(&curr) {
+            let mut path = vec![curr];
+            let mut back = curr;
+            while *costs.get(back).unwrap() > C::default() {
+                back = neighbours(&back)
+                    .into_iter()
+                    .find(|prev| {
+                        *reached.get(*prev).unwrap()
+                            && *costs.get(*prev).unwrap() + cost(prev, &back)
+                                == *costs.get(back).unwrap()
+                    })
+                    .expect("a reached point has a neighbour it was reached from");
+                path.push(back);
+            }
+            path.reverse();
+
+            return Some(Found { cost: so_far, path });
+        }
+
+        for next in neighbours(&curr) {
+            let total = so_far + cost(&curr, &next);
+            let seen = reached.get_mut(next).unwrap();
+            let known = costs.get_mut(next).unwrap();
+
+            if !*seen || total < *known {
+                *seen = true;
+                *known = total;
+                queue.push(Reverse((total, next)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{astar, bfs, dijkstra, dijkstra_dense};
+    use crate::utils::board::{Board, Neighbourhood, Point};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_bfs() {
+        let graph: HashMap<&str, Vec<&str>> = [
+            ("start", vec!["a", "b"]),
+            ("a", vec!["c"]),
+            ("b", vec!["end"]),
+            ("c", vec!["end"]),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let next = |cave: &&str| graph.get(cave).cloned().unwrap_or_default();
+
+        let found = bfs(vec!["start"], next, |&cave| cave == "end").unwrap();
+        assert_eq!(found.cost, 2);
+        assert_eq!(found.path, vec!["start", "b", "end"]);
+
+        assert!(bfs(vec!["c"], next, |&cave| cave == "a").is_none());
+    }
+
+    #[test]
+    fn test_weighted() {
+        let board = Board::<u32>::parse_digits(&["1163", "1381", "2136"]).unwrap();
+        let goal: Point = (2, 3);
+        let next = |&p: &Point| board.neighbours(p, Neighbourhood::VonNeumann);
+        let risk = |_: &Point, &to: &Point| *board.get(to).unwrap();
+
+        let found = dijkstra(vec![(0, 0)], next, risk, |&p| p == goal).unwrap();
+        assert_eq!(found.cost, 13);
+        assert_eq!(found.path.first(), Some(&(0, 0)));
+        assert_eq!(found.path.last(), Some(&goal));
+        let path_risk: u32 = found.path[1..].iter().map(|&p| board.get(p).unwrap()).sum();
+        assert_eq!(path_risk, found.cost);
+
+        let manhattan = |&p: &Point| ((goal.0 - p.0) + (goal.1 - p.1)) as u32;
+        let found_astar = astar(vec![(0, 0)], next, risk, manhattan, |&p| p == goal).unwrap();
+        assert_eq!(found_astar.cost, found.cost);
+
+        // Of paths as cheap, either one will do
+        let found_dense =
+            dijkstra_dense(board.dim(), vec![(0, 0)], next, risk, |&p| p == goal).unwrap();
+        assert_eq!(found_dense.cost, found.cost);
+        let dense_risk: u32 = found_dense.path[1..]
+            .iter()
+            .map(|&p| board.get(p).unwrap())
+            .sum();
+        assert_eq!(dense_risk, found.cost);
+
+        // Either corner will do
+        let found = dijkstra(vec![(0, 0), (2, 0)], next, risk, |&p| p == goal).unwrap();
+        assert_eq!(found.path.first(), Some(&(2, 0)));
+    }
+}
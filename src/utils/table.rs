@@ -0,0 +1,50 @@
+#[derive(Clone, Copy)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+// Prints rows as a plain-text table, sizing each column to its widest cell.
+// Cells of several lines (e.g. day 13's answer) span as many lines of the table.
+pub fn print(header: &[(&str, Align)], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = header.iter().map(|(h, _)| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            for line in cell.lines() {
+                *width = (*width).max(line.chars().count());
+            }
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let formatted: Vec<String> = cells
+            .iter()
+            .zip(header.iter().zip(widths.iter()))
+            .map(|(cell, ((_, align), width))| match align {
+                Align::Left => format!("{:<w$}", cell, w = width),
+                Align::Right => format!("{:>w$}", cell, w = width),
+            })
+            .collect();
+
+        formatted.join(" | ").trim_end().to_string()
+    };
+
+    println!("{}", format_row(header.iter().map(|(h, _)| *h).collect()));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<String>>()
+            .join("-+-")
+    );
+    for row in rows {
+        let height = row.iter().map(|c| c.lines().count()).max().unwrap_or(0);
+        for i in 0..height.max(1) {
+            println!(
+                "{}",
+                format_row(row.iter().map(|c| c.lines().nth(i).unwrap_or("")).collect())
+            );
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::utils::board::{Board, Neighbourhood, Neighbours, Point, Rect};
+
+// Boards that are built out of (parts of) other boards
+impl<T> Board<T>
+where
+    T: Clone,
+{
+    // Rows become columns
+    pub fn transpose(&self) -> Self {
+        let (height, width) = self.dim();
+        let cells = self.columns().flatten().cloned().collect();
+        Board::from_cells(cells, (width, height))
+    }
+
+    // A quarter turn clockwise
+    pub fn rotate90(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    // Mirrors left and right
+    pub fn flip_horizontal(&self) -> Self {
+        let cells = self
+            .rows()
+            .flat_map(|row| row.iter().rev())
+            .cloned()
+            .collect();
+        Board::from_cells(cells, self.dim())
+    }
+
+    // Turns the board upside down
+    pub fn flip_vertical(&self) -> Self {
+        let cells = (0..self.dim().0)
+            .rev()
+            .flat_map(|row| self.row(row).unwrap())
+            .cloned()
+            .collect();
+        Board::from_cells(cells, self.dim())
+    }
+
+    // The part of the board inside `rect`, which must fit in the board
+    pub fn crop(&self, rect: Rect) -> Self {
+        let (height, width) = self.dim();
+        assert!(
+            rect.max.0 < height && rect.max.1 < width,
+            "cannot crop {:?} out of a {}x{} board",
+            rect,
+            height,
+            width
+        );
+
+        let cells = (rect.min.0..=rect.max.0)
+            .flat_map(|row| &self.row(row).unwrap()[rect.min.1..=rect.max.1])
+            .cloned()
+            .collect();
+        Board::from_cells(cells, rect.dim())
+    }
+
+    // `nx` copies of the board across and `ny` down, with every cell of the
+    // copy in column `tile_x` and row `tile_y` given by `f`
+    pub fn tile(&self, nx: usize, ny: usize, f: impl Fn(usize, usize, &T) -> T) -> Self {
+        self.tiled(nx, ny, f).to_board()
+    }
+
+    // Copies `other` over the board, its top left corner at `at`. Whatever
+    // doesn't fit is left out.
+    pub fn paste(&mut self, at: Point, other: &Board<T>) {
+        self.paste_with(at, other, |_, new| new.clone())
+    }
+
+    // Like `paste`, with every cell set to `f(old, new)`
+    pub fn paste_with(&mut self, at: Point, other: &Board<T>, f: impl Fn(&T, &T) -> T) {
+        for ((row, col), new) in other.enumerate() {
+            if let Some(old) = self.get_mut((at.0 + row, at.1 + col)) {
+                *old = f(old, new);
+            }
+        }
+    }
+}
+
+impl<T> Board<T> {
+    // Like `tile`, without making the copies (see `Tiled`)
+    pub fn tiled<F>(&self, nx: usize, ny: usize, f: F) -> Tiled<'_, T, F>
+    where
+        F: Fn(usize, usize, &T) -> T,
+    {
+        Tiled {
+            board: self,
+            nx,
+            ny,
+            f,
+        }
+    }
+}
+
+/*
+ A read-only view of a tiled board, whose cells are only worked out as
+ they are looked at. It takes no more memory than the board it's a view
+ of, however many tiles there are.
+*/
+pub struct Tiled<'a, T, F> {
+    board: &'a Board<T>,
+    nx: usize,
+    ny: usize,
+    f: F,
+}
+
+impl<'a, T, F> Tiled<'a, T, F>
+where
+    F: Fn(usize, usize, &T) -> T,
+{
+    pub fn dim(&self) -> (usize, usize) {
+        let (height, width) = self.board.dim();
+        (height * self.ny, width * self.nx)
+    }
+
+    pub fn get(&self, p: Point) -> Option<T> {
+        let (height, width) = self.board.dim();
+        if p.0 >= height * self.ny || p.1 >= width * self.nx {
+            return None;
+        }
+
+        let cell = self.board.get((p.0 % height, p.1 % width)).unwrap();
+        Some((self.f)(p.1 / width, p.0 / height, cell))
+    }
+
+    pub fn neighbours<'h>(&self, p: Point, hood: Neighbourhood<'h>) -> Neighbours<'h> {
+        Neighbours::around(p, self.dim(), hood)
+    }
+
+    // Makes the copies after all
+    pub fn to_board(&self) -> Board<T> {
+        let (height, width) = self.dim();
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|p| self.get(p).unwrap())
+            .collect();
+
+        Board::from_cells(cells, (height, width))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::utils::board::{Board, Neighbourhood, Rect};
+
+    fn board(rows: &[&str]) -> Board<char> {
+        Board::parse_chars_with(rows, Ok).unwrap()
+    }
+
+    #[test]
+    fn test_reorient() {
+        let abc = board(&["abc", "def"]);
+
+        assert_eq!(abc.transpose(), board(&["ad", "be", "cf"]));
+        assert_eq!(abc.rotate90(), board(&["da", "eb", "fc"]));
+        assert_eq!(abc.flip_horizontal(), board(&["cba", "fed"]));
+        assert_eq!(abc.flip_vertical(), board(&["def", "abc"]));
+        assert_eq!(abc.rotate90().rotate90().rotate90().rotate90(), abc);
+    }
+
+    #[test]
+    fn test_crop_and_paste() {
+        let abc = board(&["abc", "def", "ghi"]);
+        let corner = abc.crop(Rect {
+            min: (1, 1),
+            max: (2, 2),
+        });
+        assert_eq!(corner, board(&["ef", "hi"]));
+
+        let mut dots = board(&["...", "..."]);
+        dots.paste((1, 2), &corner);
+        assert_eq!(dots, board(&["...", "..e"]));
+
+        let only_dots = |&old: &char, &new: &char| if new == '#' { new } else { old };
+        dots.paste_with((1, 1), &board(&["#.#"]), only_dots);
+        assert_eq!(dots, board(&["...", ".#e"]));
+    }
+
+    #[test]
+    fn test_tile() {
+        let digits = Board::from(vec![vec![8, 9]]);
+        let tiled = digits.tile(2, 2, |x, y, &d| (d + x + y - 1) % 9 + 1);
+
+        assert_eq!(tiled, Board::from(vec![vec![8, 9, 9, 1], vec![9, 1, 1, 2]]));
+
+        let view = digits.tiled(100, 3, |x, y, &d| (d + x + y - 1) % 9 + 1);
+        assert_eq!(view.dim(), (3, 200));
+        assert_eq!(view.get((2, 199)), Some(2));
+        assert_eq!(view.get((3, 0)), None);
+        assert_eq!(
+            view.neighbours((2, 199), Neighbourhood::VonNeumann).count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::error::Result;
+use crate::runner::{self, Outcome, Status};
+use crate::solution::Puzzle;
+use crate::utils::answers::Answers;
+use crate::utils::input::Source;
+use crate::utils::params::Params;
+use crate::utils::table::{self, Align};
+
+pub enum Verdict {
+    Correct,
+    Wrong(String),
+    Unrecorded,
+    // The part isn't solved yet, so there is nothing to check
+    Unsolved,
+    // The part didn't produce an answer at all
+    Broken(Status),
+}
+
+impl Verdict {
+    fn of(outcome: &Outcome, answers: &Answers) -> Self {
+        let expected = answers.get(outcome.day, outcome.part);
+
+        match (&outcome.status, &outcome.answer, expected) {
+            (Status::Failed(_), _, _) | (Status::Panicked(_), _, _) => {
+                Verdict::Broken(outcome.status.clone())
+            }
+            (Status::Unsolved(_), _, _) => Verdict::Unsolved,
+            (_, _, None) => Verdict::Unrecorded,
+            (_, Some(answer), Some(expected)) if answer == expected => Verdict::Correct,
+            (_, _, Some(expected)) => Verdict::Wrong(expected.to_string()),
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Wrong(_) | Verdict::Broken(_))
+    }
+}
+
+// Runs every puzzle against its real input (and with none of its params
+// set) and compares the answers to the recorded ones. Returns whether
+// everything that was recorded matched.
+pub fn verify(puzzles: &[&dyn Puzzle], answers_file: &str, jobs: usize) -> Result<bool> {
+    let answers = Answers::load(answers_file)?;
+    let outcomes = runner::run_all(
+        puzzles,
+        &Source::Default { test: false },
+        &Params::default(),
+        jobs,
+    );
+
+    let verdicts: Vec<Verdict> = outcomes.iter().map(|o| Verdict::of(o, &answers)).collect();
+    let rows: Vec<Vec<String>> = outcomes
+        .iter()
+        .zip(verdicts.iter())
+        .map(|(o, verdict)| {
+            vec![
+                o.day.to_string(),
+                o.part.to_string(),
+                o.answer.clone().unwrap_or_else(|| "-".to_string()),
+                match verdict {
+                    Verdict::Correct => "ok".to_string(),
+                    Verdict::Wrong(expected) => format!("WRONG (expected {})", expected),
+                    Verdict::Unrecorded => "no recorded answer".to_string(),
+                    Verdict::Unsolved => "not solved yet".to_string(),
+                    Verdict::Broken(status) => status.to_string(),
+                },
+            ]
+        })
+        .collect();
+
+    table::print(
+        &[
+            ("Day", Align::Right),
+            ("Part", Align::Right),
+            ("Answer", Align::Left),
+            ("Verdict", Align::Left),
+        ],
+        &rows,
+    );
+
+    let failed = verdicts.iter().filter(|v| v.is_failure()).count();
+    println!();
+    println!(
+        "{} of {} answers failed verification",
+        failed,
+        verdicts.len()
+    );
+
+    Ok(failed == 0)
+}
+
+// Runs every puzzle against its real input and stores the answers as the
+// expected ones. Parts that fail keep whatever was recorded before, while
+// parts that aren't solved yet have it dropped.
+pub fn record(puzzles: &[&dyn Puzzle], answers_file: &str, jobs: usize) -> Result<()> {
+    let mut answers = Answers::load(answers_file)?;
+    let outcomes = runner::run_all(
+        puzzles,
+        &Source::Default { test: false },
+        &Params::default(),
+        jobs,
+    );
+
+    let mut recorded = 0;
+    for outcome in outcomes {
+        match (outcome.status, outcome.answer) {
+            (Status::Ok, Some(answer)) => {
+                answers.set(outcome.day, outcome.part, answer);
+                recorded += 1;
+            }
+            (Status::Ok, None) => (),
+            // Whatever was recorded for it can't have been a real answer
+            (Status::Unsolved(_), _) => {
+                answers.remove(outcome.day, outcome.part);
+                println!(
+                    "Day {} part {} isn't solved yet, not recording it",
+                    outcome.day, outcome.part
+                );
+            }
+            (status, _) => {
+                println!(
+                    "Day {} part {} has no answer, not recording it: {}",
+                    outcome.day, outcome.part, status
+                );
+            }
+        }
+    }
+
+    answers.save(answers_file)?;
+    println!("Recorded {} answers in {}", recorded, answers_file);
+    Ok(())
+}
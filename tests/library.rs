@@ -0,0 +1,252 @@
+use aoc_2021::days;
+use aoc_2021::days::day15::safest_path;
+use aoc_2021::parsing::Parser;
+use aoc_2021::utils::board::{Board, Neighbourhood, Point};
+use aoc_2021::utils::input::{comma_list, Input, Source};
+use aoc_2021::utils::params::Params;
+use std::convert::TryFrom;
+
+#[test]
+fn test_registry() {
+    let all_days: Vec<u8> = days::all().iter().map(|p| p.day()).collect();
+
+    assert_eq!(all_days, (1..=16).collect::<Vec<u8>>());
+    assert_eq!(days::find(12).map(|p| p.name()), Some("Passage Pathing"));
+    assert!(days::find(25).is_none());
+    assert_eq!(days::latest().day(), 16);
+}
+
+#[test]
+fn test_solve_from_memory() {
+    let sonar = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+    let day1 = days::find(1).unwrap();
+
+    assert_eq!(day1.solve(Input::from_text(sonar), false).unwrap(), "7");
+    assert_eq!(day1.solve(Input::from_text(sonar), true).unwrap(), "5");
+}
+
+#[test]
+fn test_errors_point_at_the_input() {
+    let source = Source::Stdin("forward 5\nsideways 3".into());
+    let err = days::find(2)
+        .unwrap()
+        .run(false, &source, &Params::default())
+        .unwrap_err();
+    assert_eq!(err.to_string(), "<stdin>:2:1: Unknown direction: sideways");
+
+    // Line numbers are those in the file, header and all
+    let fixture = "// expect p1: 2\n#\n.";
+    let day3 = days::find(3).unwrap();
+    let err = day3.solve(Input::from_text(fixture), false);
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "2:1: '#' is not a binary digit"
+    );
+    let err = day3.solve(Input::from_text(&"1".repeat(64)), false);
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "1:32: expected at most 31 bits, found 64"
+    );
+
+    let err = days::find(7)
+        .unwrap()
+        .solve(Input::from_text("3,-5"), false);
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "1:3: a position can't be negative, found -5"
+    );
+
+    let day10 = days::find(10).unwrap();
+    let err = day10.solve(Input::from_text("()\n)"), false).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "2:1: ')' closes a bracket that was never opened"
+    );
+    let err = day10.solve(Input::from_text("(]"), true).unwrap_err();
+    assert_eq!(err.to_string(), "no answer: every line is corrupted");
+
+    let err = days::find(8)
+        .unwrap()
+        .solve(Input::from_text("ab cd | ef\nab | cd xy"), false);
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "2:9: 'xy' is not a segment pattern"
+    );
+
+    let err = days::find(14)
+        .unwrap()
+        .solve(Input::from_text("NN\n\nNN -> C\nNC => B"), false);
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "4: expected two parts separated by ' -> ', found 'NC => B'"
+    );
+
+    let err = days::find(15)
+        .unwrap()
+        .solve(Input::from_text("19\n10"), true);
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "2:2: a risk level is between 1 and 9, found 0"
+    );
+}
+
+#[test]
+fn test_input_helpers() {
+    assert_eq!(comma_list::<i32>("1,-2,3").unwrap(), vec![1, -2, 3]);
+    assert_eq!(
+        comma_list::<i32>("1,two")
+            .unwrap_err()
+            .at_line(1)
+            .to_string(),
+        "1:3: 'two' is not a valid number"
+    );
+
+    let streamed: Vec<(usize, String)> = Input::from_text("// header\na\r\n\nb\n\n")
+        .stream()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        streamed,
+        vec![
+            (2, "a".to_string()),
+            (3, String::new()),
+            (4, "b".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_board() {
+    let board = Board::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+    assert_eq!(board.dim(), (2, 3));
+    assert_eq!(board.get((1, 2)), Some(&6));
+    assert_eq!(board.get((2, 0)), None);
+    assert_eq!(
+        board.neighbours((0, 0), Neighbourhood::VonNeumann).count(),
+        2
+    );
+}
+
+#[test]
+fn test_packet_parser() {
+    assert_eq!(Parser::try_from("D2FE28").unwrap().execute().unwrap(), 2021);
+    assert_eq!(
+        Parser::try_from("8A004A801A8002F478")
+            .unwrap()
+            .versions()
+            .unwrap(),
+        16
+    );
+    assert!(Parser::try_from("D2FEXX").is_err());
+}
+
+// The lowest risk of any simple path from `p` to the bottom right, by trying them all
+fn brute_force_risk(board: &Board<u32>, p: Point, visited: &mut Vec<Point>) -> Option<u32> {
+    let (height, width) = board.dim();
+    if p == (height - 1, width - 1) {
+        return Some(0);
+    }
+
+    visited.push(p);
+    let best = board
+        .neighbours(p, Neighbourhood::VonNeumann)
+        .filter(|n| !visited.contains(n))
+        .collect::<Vec<Point>>()
+        .into_iter()
+        .filter_map(|n| Some(board.get(n).unwrap() + brute_force_risk(board, n, visited)?))
+        .min();
+    visited.pop();
+
+    best
+}
+
+// A fixed xorshift, so failures can be reproduced
+fn random(seed: u32) -> impl FnMut() -> u32 {
+    let mut state = seed;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    }
+}
+
+fn random_risks(dim: (usize, usize), random: &mut impl FnMut() -> u32) -> Board<u32> {
+    let rows = (0..dim.0)
+        .map(|_| (0..dim.1).map(|_| 1 + random() % 9).collect())
+        .collect();
+    Board::from(rows)
+}
+
+#[test]
+fn test_lowest_risk_against_brute_force() {
+    let mut random = random(2021);
+
+    for _ in 0..50 {
+        let (height, width) = (1 + random() as usize % 4, 1 + random() as usize % 4);
+        let board = random_risks((height, width), &mut random);
+
+        let found = safest_path(board.dim(), |p| *board.get(p).unwrap());
+        assert_eq!(
+            Some(found.cost),
+            brute_force_risk(&board, (0, 0), &mut vec![]),
+            "{}",
+            board
+        );
+
+        // The path is a walk through the board that adds up to the cost
+        assert_eq!(found.path.first(), Some(&(0, 0)));
+        assert_eq!(found.path.last(), Some(&(height - 1, width - 1)));
+        for step in found.path.windows(2) {
+            let distance = step[0].0.abs_diff(step[1].0) + step[0].1.abs_diff(step[1].1);
+            assert_eq!(distance, 1);
+        }
+        let risk: u32 = found.path[1..].iter().map(|&p| board.get(p).unwrap()).sum();
+        assert_eq!(risk, found.cost);
+    }
+}
+
+#[test]
+fn test_lowest_risk_of_a_large_tiling() {
+    let board = random_risks((10, 10), &mut random(15));
+    let increase = |tile_x: usize, tile_y: usize, &risk: &u32| {
+        ((risk as usize - 1 + tile_x + tile_y) % 9 + 1) as u32
+    };
+
+    // 50 tiles across and down, i.e. a 500x500 map
+    let map = board.tiled(50, 50, increase);
+    let tiles = board.tile(50, 50, increase);
+    assert_eq!(
+        safest_path(map.dim(), |p| map.get(p).unwrap()),
+        safest_path(tiles.dim(), |p| *tiles.get(p).unwrap())
+    );
+}
+
+#[test]
+fn test_params_reach_the_day() {
+    let day15 = days::find(15).unwrap();
+    let risks = "19\n13";
+    let with_tiles = |tiles: &str| {
+        let params = Params::parse(vec![tiles]).unwrap();
+        day15.solve(Input::from_text(risks).with_params(params), true)
+    };
+
+    // A single tile is the map of part 1
+    assert_eq!(with_tiles("tiles=1").unwrap(), "4");
+    assert_eq!(with_tiles("tiles=2").unwrap(), "14");
+    assert_eq!(
+        with_tiles("tiles=0").unwrap_err().to_string(),
+        "parameter tiles: there must be at least one tile"
+    );
+
+    // A bad value is the command line's fault, not the input's
+    let params = Params::parse(vec!["tiles=x"]).unwrap();
+    let err = day15
+        .run(true, &Source::Stdin(risks.into()), &params)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "parameter tiles: 'x' is not a valid number"
+    );
+}